@@ -0,0 +1,206 @@
+use log::warn;
+use semver::{Version, VersionReq};
+use std::fs;
+use std::path::Path;
+
+/// A parsed RustSec-style advisory: a Markdown file with a fenced ```toml
+/// frontmatter block declaring the affected package and its patched version
+/// ranges.
+#[derive(Debug, Clone)]
+pub struct Advisory {
+    pub id: String,
+    pub title: String,
+    pub package: String,
+    /// Semver ranges a version must satisfy at least one of to be considered
+    /// patched. Empty means no fix is available yet, so every version is
+    /// flagged.
+    pub patched: Vec<String>,
+}
+
+/// A dependency whose installed version matches an advisory's `package` and
+/// satisfies none of its `patched` ranges.
+#[derive(Debug, Clone)]
+pub struct VulnerableDependency {
+    pub name: String,
+    pub version: String,
+    pub advisory_id: String,
+    pub title: String,
+}
+
+/// Extract the contents of an advisory's fenced ```toml frontmatter block.
+///
+/// Args:
+///     - contents: The raw Markdown contents of an advisory file.
+///
+/// Returns: The text between the opening and closing ```toml fence, if present.
+fn extract_frontmatter(contents: &str) -> Option<&str> {
+    let after_fence = contents.find("```toml")? + "```toml".len();
+    let end = contents[after_fence..].find("```")?;
+    Some(contents[after_fence..after_fence + end].trim())
+}
+
+/// Parse a single advisory file, skipping (and warning about) one with no
+/// fenced frontmatter or missing required fields rather than panicking.
+///
+/// Args:
+///     - path: Path to the advisory Markdown file.
+///
+/// Returns: The parsed advisory, or `None` if it couldn't be read or parsed.
+fn parse_advisory(path: &Path) -> Option<Advisory> {
+    let contents = fs::read_to_string(path).ok()?;
+
+    let Some(frontmatter) = extract_frontmatter(&contents) else {
+        warn!("Advisory {} has no ```toml frontmatter block; skipping.", path.display());
+        return None;
+    };
+
+    let Ok(table) = frontmatter.parse::<toml::Table>() else {
+        warn!("Advisory {} has malformed toml frontmatter; skipping.", path.display());
+        return None;
+    };
+
+    let Some(advisory) = table.get("advisory").and_then(|v| v.as_table()) else {
+        warn!("Advisory {} is missing its [advisory] table; skipping.", path.display());
+        return None;
+    };
+    let Some(id) = advisory.get("id").and_then(|v| v.as_str()) else {
+        warn!("Advisory {} is missing advisory.id; skipping.", path.display());
+        return None;
+    };
+    let Some(package) = advisory.get("package").and_then(|v| v.as_str()) else {
+        warn!("Advisory {} is missing advisory.package; skipping.", path.display());
+        return None;
+    };
+    let title = advisory.get("title").and_then(|v| v.as_str()).unwrap_or_default();
+
+    // no `patched` ranges at all means no fix is available yet, so the
+    // advisory should flag every version of the package.
+    let patched = table
+        .get("versions")
+        .and_then(|v| v.as_table())
+        .and_then(|versions| versions.get("patched"))
+        .and_then(|v| v.as_array())
+        .map(|ranges| ranges.iter().filter_map(|v| v.as_str().map(str::to_string)).collect())
+        .unwrap_or_default();
+
+    Some(Advisory {
+        id: id.to_string(),
+        title: title.to_string(),
+        package: package.to_string(),
+        patched,
+    })
+}
+
+/// Load every advisory found directly under `advisory_db`, skipping (and
+/// warning about) any file that isn't a valid advisory rather than aborting
+/// the whole scan.
+///
+/// Args:
+///     - advisory_db: The directory of advisory Markdown files.
+///
+/// Returns: The successfully parsed advisories.
+pub fn load_advisories(advisory_db: &Path) -> Vec<Advisory> {
+    let Ok(entries) = fs::read_dir(advisory_db) else {
+        warn!("Could not read advisory directory {}.", advisory_db.display());
+        return Vec::new();
+    };
+
+    entries
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().and_then(|ext| ext.to_str()) == Some("md"))
+        .filter_map(|path| parse_advisory(&path))
+        .collect()
+}
+
+/// Whether `version` satisfies at least one of `patched`'s semver ranges.
+///
+/// Args:
+///     - version: The installed dependency version, e.g. `"1.2.3"`.
+///     - patched: The advisory's patched ranges, e.g. `[">= 1.2.3"]`.
+///
+/// Returns: `Err` if `version` itself fails to parse as semver, so a garbled
+///     dependency version is surfaced rather than silently treated as unpatched.
+fn is_patched(version: &str, patched: &[String]) -> Result<bool, semver::Error> {
+    let parsed = Version::parse(version)?;
+    Ok(patched
+        .iter()
+        .any(|range| VersionReq::parse(range).is_ok_and(|req| req.matches(&parsed))))
+}
+
+/// Cross-reference `dependencies` against `advisories`, reporting every
+/// dependency whose version matches an advisory's package and satisfies none
+/// of its `patched` ranges.
+///
+/// Args:
+///     - advisories: The loaded advisory database.
+///     - dependencies: The name/version pairs of resolved dependencies to check.
+///
+/// Returns: The unpatched matches, each naming the advisory that flagged it.
+///     A dependency appears once per advisory it's vulnerable to. A version
+///     that fails to parse as semver is printed as an error and excluded
+///     rather than silently skipped.
+pub fn check_advisories(
+    advisories: &[Advisory],
+    dependencies: &[(String, String)],
+) -> Vec<VulnerableDependency> {
+    let mut vulnerable = Vec::new();
+    for (name, version) in dependencies {
+        for advisory in advisories.iter().filter(|advisory| &advisory.package == name) {
+            match is_patched(version, &advisory.patched) {
+                Ok(true) => {}
+                Ok(false) => vulnerable.push(VulnerableDependency {
+                    name: name.clone(),
+                    version: version.clone(),
+                    advisory_id: advisory.id.clone(),
+                    title: advisory.title.clone(),
+                }),
+                Err(err) => {
+                    eprintln!(
+                        "Failed to parse version {version:?} for {name} against advisory {}: {err}",
+                        advisory.id
+                    );
+                }
+            }
+        }
+    }
+    vulnerable
+}
+
+#[test]
+fn test_extract_frontmatter_missing_fence() {
+    assert_eq!(extract_frontmatter("# Advisory\n\nNo frontmatter here."), None);
+}
+
+#[test]
+fn test_parse_advisory_malformed_frontmatter() {
+    use std::io::Write;
+    use tempfile::NamedTempFile;
+
+    let mut no_fence = NamedTempFile::new().unwrap();
+    write!(no_fence, "# Advisory\n\nNo frontmatter here.").unwrap();
+    assert!(parse_advisory(no_fence.path()).is_none());
+
+    let mut bad_toml = NamedTempFile::new().unwrap();
+    write!(bad_toml, "```toml\nthis = is not [valid\n```").unwrap();
+    assert!(parse_advisory(bad_toml.path()).is_none());
+
+    let mut missing_fields = NamedTempFile::new().unwrap();
+    write!(
+        missing_fields,
+        "```toml\n[advisory]\ntitle = \"Missing id and package\"\n```"
+    )
+    .unwrap();
+    assert!(parse_advisory(missing_fields.path()).is_none());
+
+    let mut valid = NamedTempFile::new().unwrap();
+    write!(
+        valid,
+        "```toml\n[advisory]\nid = \"RUSTSEC-0000-0000\"\npackage = \"demo\"\ntitle = \"Demo advisory\"\n\n[versions]\npatched = [\">=1.2.3\"]\n```"
+    )
+    .unwrap();
+    let advisory = parse_advisory(valid.path()).unwrap();
+    assert_eq!(advisory.id, "RUSTSEC-0000-0000");
+    assert_eq!(advisory.package, "demo");
+    assert_eq!(advisory.patched, vec![">=1.2.3".to_string()]);
+}
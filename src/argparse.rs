@@ -1,4 +1,26 @@
-use clap::{Parser, Subcommand};
+use clap::{Parser, Subcommand, ValueEnum};
+use std::path::PathBuf;
+
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+/// Output format for `licensepy check`.
+pub enum OutputFormat {
+    /// Colorized, human-readable output (the default).
+    Text,
+    /// A JSON array of the resolved dependency metadata.
+    Json,
+    /// An SPDX-style bill-of-materials document.
+    Spdx,
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+/// Output format for `licensepy format --check`.
+pub enum CheckFormat {
+    /// A unified `---`/`+++` diff of the edit each non-compliant file needs
+    /// (the default).
+    Diff,
+    /// A JSON array of `{path, status, insert_offset}` for every file checked.
+    Json,
+}
 
 #[derive(Parser)]
 #[command(version, about, long_about = None)]
@@ -35,6 +57,20 @@ pub enum Commands {
         /// Number of threads to use. Max is 32.
         #[arg(short = 'j', long, default_value_t = 1)]
         num_threads: u8,
+
+        /// Output format: text, json, or spdx. Default is text.
+        #[arg(long, value_enum, default_value_t = OutputFormat::Text)]
+        output_format: OutputFormat,
+
+        /// Extras to evaluate `extra == '...'` markers against when recursively
+        /// resolving requirements, e.g. `--extras test,docs`. Default is none.
+        #[arg(long, value_delimiter = ',')]
+        extras: Vec<String>,
+
+        /// Write a self-contained HTML license report (summary table plus
+        /// verbatim license texts) to this path. Default is not to write one.
+        #[arg(long)]
+        report: Option<PathBuf>,
     },
 
     /// Run license header formatter.
@@ -53,6 +89,21 @@ pub enum Commands {
         #[arg(short = 'y', long)]
         license_year: Option<u16>,
 
+        /// Always use the current calendar year for `{year}`, overriding
+        /// `license_year` from config or `--license-year`. Default is false.
+        #[arg(long, default_value_t = false)]
+        use_current_year: bool,
+
+        /// Recognize and manage `SPDX-FileCopyrightText:`/`SPDX-License-Identifier:`
+        /// tag lines instead of matching `license_header_template`. Default is false.
+        #[arg(long, default_value_t = false)]
+        spdx: bool,
+
+        /// Delete a recognized license header instead of inserting or updating
+        /// one, e.g. when migrating between license texts. Default is false.
+        #[arg(long, default_value_t = false)]
+        remove: bool,
+
         /// Don't print any outputs. Default if false.
         #[arg(short, long, default_value_t = false)]
         silent: bool,
@@ -61,6 +112,19 @@ pub enum Commands {
         #[arg(short, long, default_value_t = false)]
         dry_run: bool,
 
+        /// Check header compliance without rewriting any file: writes nothing
+        /// and exits with the number of non-compliant files, reporting each
+        /// one through `--check-format` instead of the usual per-file
+        /// messages. Useful for gating CI on header compliance. Default is false.
+        #[arg(long, default_value_t = false)]
+        check: bool,
+
+        /// Output format for `--check`: a human-readable unified diff of the
+        /// edit each non-compliant file needs, or a JSON summary. Ignored
+        /// without `--check`. Default is diff.
+        #[arg(long, value_enum, default_value_t = CheckFormat::Diff)]
+        check_format: CheckFormat,
+
         /// Number of threads to use. Default is 1. Max is 32.
         #[arg(short = 'j', long, default_value_t = 1)]
         num_threads: u8,
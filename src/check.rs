@@ -2,16 +2,21 @@ use crate::metadata::Metadata;
 use colored::Colorize;
 use log::debug;
 use rayon::prelude::*;
-use regex::Regex;
 use std::fs::File;
 use std::fs::{DirEntry, read_dir};
 use std::io::{self, BufRead};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::process::Command;
 use std::process::exit;
 
+use crate::advisory;
+use crate::argparse::OutputFormat;
+use crate::markers;
 use crate::print_output::{print_by_license, print_by_package};
+use crate::report::{print_json, print_spdx};
+use crate::spdx;
 use crate::utils::{Config, get_python_version, read_config};
+use std::collections::{HashMap, HashSet};
 
 #[derive(Debug, Clone)]
 /// Enum to store the distribution type where the package is stored.
@@ -26,29 +31,75 @@ impl DistType {
     /// in an environment.
     ///
     /// Args:
-    ///     - python_version: The python version in the cwd.
+    ///     - env: The marker-evaluation environment (Python version, platform, extras).
     ///     - recursive: Whether to get the metadata for the dependencies required by
     ///         of the current being parsed as well.
     ///     - licenses_to_avoid: Array of licenses to avoid.
+    ///     - licenses_to_allow: Array of licenses explicitly allowed, letting a
+    ///         compound expression pass on an allowed branch.
+    ///     - exceptions: Per-package license tolerances from config, mapping a
+    ///         package name to a license (or `"*"`) that is grandfathered in
+    ///         for that package only.
+    ///     - clarifications: Per-package license overrides/exceptions from config.
+    ///     - default_bsd_license: The SPDX id the ambiguous `"BSD License"`
+    ///         classifier canonicalizes to, from `config.default_bsd_license`.
     ///
     /// Returns: The Metadata for the package.
+    #[allow(clippy::too_many_arguments)]
     pub fn get_metadata(
         self,
-        python_version: &[i32; 3],
+        env: &crate::markers::Environment,
         recursive: bool,
         licenses_to_avoid: &[String],
+        licenses_to_allow: &[String],
+        exceptions: &HashMap<String, String>,
+        clarifications: &[crate::utils::Clarification],
+        default_bsd_license: &str,
     ) -> Metadata {
         match self {
             DistType::EggDir(path) => {
                 let metadata_path = path.join("PKG-INFO");
-                parse_metadata(metadata_path, python_version, recursive, licenses_to_avoid)
+                parse_metadata(
+                    metadata_path,
+                    &path,
+                    env,
+                    recursive,
+                    licenses_to_avoid,
+                    licenses_to_allow,
+                    exceptions,
+                    clarifications,
+                    default_bsd_license,
+                )
             }
             DistType::DistDir(path) => {
                 let metadata_path = path.join("METADATA");
-                parse_metadata(metadata_path, python_version, recursive, licenses_to_avoid)
+                parse_metadata(
+                    metadata_path,
+                    &path,
+                    env,
+                    recursive,
+                    licenses_to_avoid,
+                    licenses_to_allow,
+                    exceptions,
+                    clarifications,
+                    default_bsd_license,
+                )
             }
             DistType::Info(path) => {
-                parse_metadata(path, python_version, recursive, licenses_to_avoid)
+                // `Info` distributions are a single metadata file rather than a
+                // directory; sibling LICENSE files, if any, live alongside it.
+                let license_dir = path.parent().map(PathBuf::from).unwrap_or_default();
+                parse_metadata(
+                    path,
+                    &license_dir,
+                    env,
+                    recursive,
+                    licenses_to_avoid,
+                    licenses_to_allow,
+                    exceptions,
+                    clarifications,
+                    default_bsd_license,
+                )
             }
         }
     }
@@ -139,27 +190,58 @@ pub fn get_dist_directories() -> Vec<String> {
     dist_dirs
 }
 
+/// Build an `OR` chain over classifier-derived license strings, treating
+/// multiple `Classifier: License ::` lines as dual-licensing alternatives.
+///
+/// Returns `None` if there are no license strings to combine.
+fn classifier_expression(licenses: &[String]) -> Option<spdx::SpdxExpr> {
+    let mut ids = licenses.iter().map(|l| spdx::SpdxExpr::Id(l.clone()));
+    let first = ids.next()?;
+    ids.fold(first, |acc, id| spdx::SpdxExpr::Or(Box::new(acc), Box::new(id)))
+        .into()
+}
+
 /// Parse metadata file for a package.
 ///
 /// Args:
 ///     - path: Path to the metadata file.
-///     - python_version: Version of Python3 in the cwd.
+///     - env: The marker-evaluation environment (Python version, platform, extras).
 ///     - recursive: Whether to get the metadata for the dependencies required by
 ///         of the current being parsed as well.
 ///     - licenses_to_avoid: Array of licenses to avoid.
+///     - licenses_to_allow: Array of licenses explicitly allowed, letting a
+///         compound expression pass on an allowed branch.
+///     - exceptions: Per-package license tolerances from config, mapping a
+///         package name to a license (or `"*"`) that is grandfathered in
+///         for that package only.
+///     - clarifications: Per-package license overrides/exceptions from config.
+///     - license_dir: The directory to fall back to scanning for LICENSE files
+///         in if the metadata declares no license.
+///     - default_bsd_license: The SPDX id the ambiguous `"BSD License"`
+///         classifier canonicalizes to, from `config.default_bsd_license`.
 ///
 /// Returns: Metadata struct with the field filled with extracted information.
+#[allow(clippy::too_many_arguments)]
 fn parse_metadata(
     path: PathBuf,
-    python_version: &[i32; 3],
+    license_dir: &Path,
+    env: &markers::Environment,
     recursive: bool,
     license_to_avoid: &[String],
+    license_to_allow: &[String],
+    exceptions: &HashMap<String, String>,
+    clarifications: &[crate::utils::Clarification],
+    default_bsd_license: &str,
 ) -> Metadata {
     // requirements for the package
     let mut requirements: Vec<String> = Vec::new();
     let mut name: String = String::new();
+    let mut version: String = String::new();
 
     let mut license: Vec<String> = Vec::new();
+    // the raw `License-Expression:` value, kept separate from classifier-derived
+    // license strings so it can be parsed as an SPDX expression.
+    let mut license_expression: Option<String> = None;
     // closure for cleaning lines from metadata file.
     // Splits by delimiter and returns the last or first element trimmed as an String.
     let clean_line = move |line: &str, del: &[char], first: bool| {
@@ -174,132 +256,334 @@ fn parse_metadata(
 
     let file = File::open(path).unwrap();
     for line in io::BufReader::new(file).lines().map_while(Result::ok) {
-        if line.starts_with("License-Expression: ")
-            || line.starts_with("Classifier: License :: OSI Approved :: ")
-        {
-            // handling cases like => License: BSD and License-Expression: BSD or
+        if line.starts_with("License-Expression: ") {
+            // the SPDX expression, e.g. License-Expression: MIT OR Apache-2.0
+            let expr = clean_line(&line, &[':'], false);
+            license.push(expr.clone());
+            license_expression = Some(expr);
+        } else if line.starts_with("Classifier: License :: OSI Approved :: ") {
             // handling cases like => Classifier: License :: OSI Approved :: BSD License
             // could be multiple
             license.push(clean_line(&line, &[':'], false));
         } else if line.starts_with("Name: ") {
             // handling cases like => Name: numpy
             name = clean_line(&line, &[':'], false);
+        } else if line.starts_with("Version: ") {
+            // handling cases like => Version: 1.2.3
+            version = clean_line(&line, &[':'], false);
         } else if line.starts_with("Requires-Dist: ") && recursive {
             // handling cases like => Requires-Dist: coverage ; extra == 'test'
-            // ignore if not recursively handling
-            if line.contains("extra") {
-                // ignore extra requirement
-                continue;
-            }
             let req_info = clean_line(&line, &[':'], false);
-            if !req_info.contains(";") {
-                // extracts the name of the requirement.
-                let req = clean_line(&req_info, &['<', '>', '=', '~', '(', ';', '!'], true);
-
-                debug!("Requirement {req:?}.");
-                requirements.push(req);
-            } else if req_info.contains("; python_version") {
-                // if there is a python version stated for the requirement, check that it
-                // is met by the python version in the cwd.
-                let py_req = clean_line(&req_info, &[';'], false);
-
-                if !meets_python_req(&py_req, python_version) {
+            if let Some((req_part, marker)) = req_info.split_once(';') {
+                // evaluate the marker (python_version, sys_platform, extra, etc.)
+                // against the current environment before counting the requirement.
+                if !markers::eval_marker_str(marker.trim(), env) {
                     continue;
                 }
+                let req = clean_line(req_part, &['<', '>', '=', '~', '(', ';', '!'], true);
+                debug!("Requirement {req:?}.");
+                requirements.push(req);
+            } else {
+                // extracts the name of the requirement.
                 let req = clean_line(&req_info, &['<', '>', '=', '~', '(', ';', '!'], true);
+                debug!("Requirement {req:?}.");
                 requirements.push(req);
             }
         }
     }
 
-    if license.is_empty() {
-        Metadata {
-            name,
-            license: vec!["?".to_string()],
-            requirements,
-            bad_license: false,
+    // verbatim text of the first on-disk LICENSE-like file, if any, for the
+    // `--report` HTML appendix — captured regardless of how the license
+    // itself was determined below.
+    let license_text = crate::license_file::find_license_files(license_dir)
+        .into_iter()
+        .find_map(|p| std::fs::read_to_string(p).ok());
+
+    let avoid_set: HashSet<String> = license_to_avoid.iter().cloned().collect();
+    let allow_set: HashSet<String> = license_to_allow.iter().cloned().collect();
+
+    let metadata = if license.is_empty() {
+        // no declared license in metadata; fall back to scanning sibling LICENSE
+        // files in the dist-info/egg-info directory.
+        match crate::license_file::discover_license(license_dir) {
+            Some((source, spdx_id, inferred)) => {
+                let bad_license =
+                    !spdx::allowed(&spdx::SpdxExpr::Id(spdx_id.clone()), &allow_set, &avoid_set);
+                Metadata {
+                    name,
+                    version,
+                    license: vec![spdx_id.clone()],
+                    license_canonical: vec![spdx_id.clone()],
+                    license_expr: spdx::parse_expression(&spdx_id),
+                    license_source: Some(source),
+                    license_inferred: inferred,
+                    license_text,
+                    requirements,
+                    bad_license,
+                    waived: false,
+                    required_by: vec![],
+                }
+            }
+            None => Metadata {
+                name,
+                version,
+                license: vec!["?".to_string()],
+                license_canonical: vec!["?".to_string()],
+                license_expr: None,
+                license_source: None,
+                license_inferred: false,
+                license_text,
+                requirements,
+                bad_license: false,
+                waived: false,
+                required_by: vec![],
+            },
         }
     } else {
         // choose either the license or license_classifier
         license.sort();
         license.dedup();
-        let bad_license = license.iter().any(|item| license_to_avoid.contains(item));
+
+        // map trove-classifier/legacy spellings (e.g. "BSD License") to their
+        // canonical SPDX id so they line up with `license_to_avoid` entries
+        // and with packages reporting the same license via `License-Expression:`.
+        let license_canonical: Vec<String> = license
+            .iter()
+            .map(|item| crate::metadata::canonicalize_license(item, default_bsd_license))
+            .collect();
+
+        // prefer the parsed `License-Expression:` AST; fall back to treating the
+        // canonicalized classifier licenses as alternatives (`OR`), since
+        // multiple `Classifier: License ::` lines conventionally mean the
+        // package is dual-licensed under any one of them.
+        let license_expr = license_expression
+            .as_deref()
+            .and_then(spdx::parse_expression)
+            .or_else(|| classifier_expression(&license_canonical));
+        let bad_license = match &license_expr {
+            Some(expr) => !spdx::allowed(expr, &allow_set, &avoid_set),
+            None => license_canonical.iter().any(|item| license_to_avoid.contains(item)),
+        };
+
         Metadata {
             name,
+            version,
             license,
+            license_canonical,
+            license_expr,
+            license_source: None,
+            license_inferred: false,
+            license_text,
             requirements,
             bad_license,
+            waived: false,
+            required_by: vec![],
         }
+    };
+
+    let parsed_version = crate::utils::parse_version(&metadata.version, &[0, 0, 0]);
+    let metadata = apply_clarifications(
+        metadata,
+        &parsed_version,
+        clarifications,
+        license_to_avoid,
+        license_to_allow,
+        license_dir,
+        default_bsd_license,
+    );
+    apply_exceptions(metadata, exceptions)
+}
+
+/// Apply any matching `[tool.licensepy.exceptions]` entry, tolerating an
+/// otherwise-forbidden license for a single named package.
+///
+/// Unlike a clarification, an exception never overrides the detected license
+/// or records a waiver — it's a flat `name -> license` (or `name -> "*"`)
+/// grandfathering list, applied after clarifications so it can still rescue
+/// a package a clarification left `bad_license`.
+///
+/// Args:
+///     - metadata: The metadata to apply exceptions to.
+///     - exceptions: The package name -> tolerated license map from config.
+fn apply_exceptions(mut metadata: Metadata, exceptions: &HashMap<String, String>) -> Metadata {
+    if metadata.bad_license
+        && let Some(tolerated) = exceptions.get(&metadata.name)
+        && (tolerated == "*" || metadata.license_canonical.iter().any(|item| item == tolerated))
+    {
+        metadata.bad_license = false;
     }
+    metadata
 }
 
-/// Extract Python version from the string used to denote version restriction in metadata
-/// (ie for "...>=3.9" the string "3.9" is provided to the function and returns [3.9.0]
-/// if 0 is the patch version provided in the `python_version`). If the minor and/or patch
-/// version are not found in the string, they are replaced by the respective versions
-/// from the python_version.
+/// Check whether the project's own declared `project.license` (PEP 621/639,
+/// from [`Config::project_license`]) is itself flagged by its own
+/// `avoid`/`allow` policy, catching the case of a project that bans a
+/// license in its dependencies while shipping under that same license.
 ///
 /// Args:
-///     - version: The version string found in metadata.
-///     - python_version: The version of Python in the cwd.
+///     - project_license: The project's own declared SPDX license expression.
+///     - license_to_avoid: Array of licenses to avoid, from config.
+///     - license_to_allow: Array of licenses explicitly allowed, from config.
 ///
-/// Returns: An array of the major, minor, patch version extracted from the version string.
+/// Returns: A warning message if the project's own license doesn't clear its
+///     own policy, or if the expression fails to parse; `None` otherwise.
+fn check_project_license_policy(
+    project_license: &str,
+    license_to_avoid: &[String],
+    license_to_allow: &[String],
+) -> Option<String> {
+    let Some(expr) = spdx::parse_expression(project_license) else {
+        return Some(format!(
+            "This project declares an unparsable license expression: {project_license:?}."
+        ));
+    };
+
+    let avoid_set: HashSet<String> = license_to_avoid.iter().cloned().collect();
+    let allow_set: HashSet<String> = license_to_allow.iter().cloned().collect();
+    if spdx::allowed(&expr, &allow_set, &avoid_set) {
+        None
+    } else {
+        Some(format!(
+            "This project is declared as {project_license}, which its own avoid/allow policy \
+             flags."
+        ))
+    }
+}
+
+/// Apply any matching per-package clarifications to a parsed `Metadata`,
+/// overriding its license and/or waiving a forbidden-license failure.
 ///
-fn parse_version(version: &str, python_version: &[i32; 3]) -> [i32; 3] {
-    let mut parsed_version: Vec<i32> = version
-        .split('.')
-        .enumerate()
-        .map(|(index, s)| s.parse::<i32>().unwrap_or(python_version[index]))
-        .collect();
+/// A clarification applies when its `package` matches `metadata.name`, it has
+/// no `version_req` or the given `version` meets it, and — if it sets
+/// `expected_file_hash` — the package's on-disk LICENSE content still hashes
+/// to that value (otherwise the clarification is skipped, since the license
+/// may have changed since the override was recorded). The most recently
+/// matching entry wins; license overrides are applied before `bad_license` is
+/// recomputed, and waived failures are recorded so output can show which
+/// failures were waived by an exception.
+///
+/// Args:
+///     - metadata: The metadata to apply clarifications to.
+///     - version: The parsed version of the package, used to evaluate version_req.
+///     - clarifications: The clarifications from config.
+///     - license_to_avoid: Array of licenses to avoid.
+///     - license_to_allow: Array of licenses explicitly allowed, letting a
+///         compound expression pass on an allowed branch.
+///     - license_dir: The package's dist-info/egg-info directory, checked
+///         against `expected_file_hash` when a clarification sets one.
+///     - default_bsd_license: The SPDX id the ambiguous `"BSD License"`
+///         classifier canonicalizes to, from `config.default_bsd_license`.
+#[allow(clippy::too_many_arguments)]
+fn apply_clarifications(
+    mut metadata: Metadata,
+    version: &[i32; 3],
+    clarifications: &[crate::utils::Clarification],
+    license_to_avoid: &[String],
+    license_to_allow: &[String],
+    license_dir: &Path,
+    default_bsd_license: &str,
+) -> Metadata {
+    for clarification in clarifications {
+        if clarification.package != metadata.name {
+            continue;
+        }
+        if let Some(version_req) = &clarification.version_req
+            && !crate::utils::meets_version_req(version_req, version)
+        {
+            continue;
+        }
+        if let Some(expected_hash) = &clarification.expected_file_hash
+            && crate::license_file::content_hash(license_dir).as_ref() != Some(expected_hash)
+        {
+            // on-disk LICENSE content no longer matches what the clarification
+            // was recorded against; don't let a stale override mask the change.
+            continue;
+        }
 
-    let mut diff = 3 - parsed_version.len();
+        if let Some(license) = &clarification.license {
+            metadata.license = vec![license.clone()];
+            metadata.license_canonical =
+                vec![crate::metadata::canonicalize_license(license, default_bsd_license)];
+            metadata.license_expr = spdx::parse_expression(license);
+        }
 
-    // if the any of the version numbers are missing, replace with the respective
-    // version number from the python_version
-    while diff > 0 {
-        parsed_version.push(python_version[3 - diff]);
-        diff -= 1;
+        if clarification.allow {
+            metadata.waived = true;
+            metadata.bad_license = false;
+        } else {
+            metadata.waived = false;
+            let avoid_set: HashSet<String> = license_to_avoid.iter().cloned().collect();
+            let allow_set: HashSet<String> = license_to_allow.iter().cloned().collect();
+            metadata.bad_license = metadata.license_canonical.iter().any(|item| {
+                !spdx::allowed(&spdx::SpdxExpr::Id(item.clone()), &allow_set, &avoid_set)
+            });
+        }
     }
-
-    parsed_version.try_into().unwrap()
+    metadata
 }
 
-/// Check if a provided constraint for a package is met by a python_version.
+/// Resolve the full transitive dependency closure for every package in
+/// `dependencies` by following `requirements` edges with a worklist/BFS over a
+/// PEP 503 name index, skipping already-visited nodes to handle cycles.
+///
+/// Replaces each package's `requirements` with its complete transitive
+/// closure (instead of just its direct `Requires-Dist` names), and records on
+/// every `bad_license` package which top-level packages' closures reach it, so
+/// a user can see which dependency is responsible for pulling it in.
 ///
 /// Args:
-///     - constraint: the constraint for a package.
-///     - python_version: the Python3 version in the cwd.
+///     - dependencies: The directly-parsed metadata for every distribution found.
 ///
-/// Returns: Whether the version constraint was met.
-fn meets_python_req(constraint: &str, python_version: &[i32; 3]) -> bool {
-    let cleaned_constraint = constraint
-        .replace(' ', "")
-        .replace("\'", "")
-        .replace("\"", "");
-
-    let re = Regex::new(r#"(==|<=|>=|!=|<|>)(\d+\.\d+(?:\.\d+)?)"#).unwrap();
-    if let Some(caps) = re.captures(&cleaned_constraint) {
-        // use regex to extract the operator and version string.
-        let operator = &caps[1];
-        let version_str = &caps[2];
-
-        let constraint_version = parse_version(version_str, python_version);
-        debug!(
-            "Operator {operator:?} | Version_string {version_str:?} | new Version {constraint_version:?}."
-        );
-
-        match operator {
-            "<=" => *python_version <= constraint_version,
-            ">=" => *python_version >= constraint_version,
-            "<" => *python_version < constraint_version,
-            ">" => *python_version > constraint_version,
-            "==" => *python_version == constraint_version,
-            "!=" => *python_version != constraint_version,
-            _ => false,
+/// Returns: `dependencies` with `requirements` expanded to the full transitive
+///     closure and `required_by` filled in.
+fn resolve_transitive_dependencies(mut dependencies: Vec<Metadata>) -> Vec<Metadata> {
+    use crate::utils::normalize_pep503_name;
+    use std::collections::VecDeque;
+
+    let name_index: std::collections::HashMap<String, usize> = dependencies
+        .iter()
+        .enumerate()
+        .map(|(i, dep)| (normalize_pep503_name(&dep.name), i))
+        .collect();
+    let names: Vec<String> = dependencies.iter().map(|dep| dep.name.clone()).collect();
+
+    // full transitive closure of each package, by index
+    let closures: Vec<Vec<usize>> = (0..dependencies.len())
+        .map(|root| {
+            let mut visited: HashSet<usize> = HashSet::from([root]);
+            let mut queue: VecDeque<usize> = VecDeque::from([root]);
+            let mut closure: Vec<usize> = Vec::new();
+            while let Some(cur) = queue.pop_front() {
+                for req in &dependencies[cur].requirements {
+                    if let Some(&idx) = name_index.get(&normalize_pep503_name(req))
+                        && visited.insert(idx)
+                    {
+                        closure.push(idx);
+                        queue.push_back(idx);
+                    }
+                }
+            }
+            closure
+        })
+        .collect();
+
+    // for every bad-license package, record which top-level packages'
+    // closures reach it
+    let mut required_by: Vec<Vec<String>> = vec![Vec::new(); dependencies.len()];
+    for (root, closure) in closures.iter().enumerate() {
+        for &idx in closure {
+            if dependencies[idx].bad_license {
+                required_by[idx].push(names[root].clone());
+            }
         }
-    } else {
-        false
     }
+
+    for (i, dep) in dependencies.iter_mut().enumerate() {
+        dep.requirements = closures[i].iter().map(|&idx| names[idx].clone()).collect();
+        dep.required_by = std::mem::take(&mut required_by[i]);
+    }
+
+    dependencies
 }
 
 /// Run the license checker by extracting all the package info including licenses.
@@ -312,12 +596,19 @@ fn meets_python_req(constraint: &str, python_version: &[i32; 3]) -> bool {
 ///     - silent: Whether to print results of checks.
 ///     - fail_print: Whether to only print the failures (when a license flagged to avoid
 ///         is found).
+///     - extras: Extras to select when evaluating `extra == '...'` markers on
+///         `Requires-Dist` lines.
+///     - report: If set, write a self-contained HTML license report to this path.
+#[allow(clippy::too_many_arguments)]
 pub fn run_check(
     recursive: bool,
     by_package: bool,
     ignore_toml: bool,
     silent: bool,
     fail_print: bool,
+    output_format: OutputFormat,
+    extras: &[String],
+    report: Option<&Path>,
 ) {
     let config: Config = if ignore_toml {
         Config::default()
@@ -325,6 +616,12 @@ pub fn run_check(
         read_config()
     };
     let license_to_avoid: Vec<String> = config.avoid;
+    let license_to_allow: Vec<String> = config.allow;
+    let exceptions = config.exceptions;
+    let clarifications = config.clarifications;
+    let advisory_db = config.advisory_db;
+    let project_license = config.project_license;
+    let default_bsd_license = config.default_bsd_license;
     // get the python version in the cwd
     let python_version: [i32; 3] = get_python_version();
     let str_version = python_version
@@ -332,12 +629,14 @@ pub fn run_check(
         .map(|n| format!("{n}"))
         .collect::<Vec<_>>()
         .join(".");
+    let env = markers::Environment::current(python_version, extras.iter().cloned().collect());
 
     // get the distribution directories
     let dist_dirs = get_dist_directories();
 
     if !silent {
         println!("Avoid {license_to_avoid:?}");
+        println!("Allow {license_to_allow:?}");
         println!("PYTHON VERSION {str_version:}");
         println!("Dependencies stored at {dist_dirs:#?}.");
         println!();
@@ -354,10 +653,27 @@ pub fn run_check(
     let dependencies: Vec<Metadata> = package_dist
         .par_iter()
         .cloned()
-        .map(|dist| dist.get_metadata(&python_version, recursive, &license_to_avoid))
+        .map(|dist| {
+            dist.get_metadata(
+                &env,
+                recursive,
+                &license_to_avoid,
+                &license_to_allow,
+                &exceptions,
+                &clarifications,
+                &default_bsd_license,
+            )
+        })
         .collect();
     debug!("{dependencies:?}.");
 
+    // expand direct Requires-Dist names into the full transitive closure
+    let dependencies = if recursive {
+        resolve_transitive_dependencies(dependencies)
+    } else {
+        dependencies
+    };
+
     let num_dep = dependencies.len();
     let num_bad_license: i32 = dependencies
         .iter()
@@ -366,18 +682,63 @@ pub fn run_check(
         .try_into()
         .unwrap();
 
+    let license_file_warnings = crate::license_file::check_project_license_files(
+        Path::new("."),
+        &license_to_avoid,
+        &license_to_allow,
+    );
     if !silent {
-        if by_package {
-            print_by_package(dependencies, recursive, fail_print);
-        } else {
-            print_by_license(dependencies, &license_to_avoid, recursive, fail_print);
+        for warning in &license_file_warnings {
+            println!("Warning: {warning}");
+        }
+    }
+
+    if let Some(project_license) = &project_license
+        && let Some(warning) =
+            check_project_license_policy(project_license, &license_to_avoid, &license_to_allow)
+        && !silent
+    {
+        println!("Warning: {warning}");
+    }
+
+    if let Some(advisory_db) = &advisory_db {
+        let advisories = advisory::load_advisories(Path::new(advisory_db));
+        let installed: Vec<(String, String)> =
+            dependencies.iter().map(|dep| (dep.name.clone(), dep.version.clone())).collect();
+        let vulnerable = advisory::check_advisories(&advisories, &installed);
+
+        if !silent && !vulnerable.is_empty() {
+            println!("Found {} dependencies with unpatched advisories:", vulnerable.len());
+            for dep in &vulnerable {
+                println!("  {} {} - {} ({})", dep.name, dep.version, dep.advisory_id, dep.title);
+            }
+            println!();
+        }
+    }
+
+    if let Some(report_path) = report
+        && let Err(err) = crate::html_report::write_report(report_path, &dependencies)
+    {
+        eprintln!("Failed to write HTML report to {}: {err}", report_path.display());
+    }
+
+    if !silent {
+        match output_format {
+            OutputFormat::Json => print_json(&dependencies),
+            OutputFormat::Spdx => print_spdx(&dependencies, recursive),
+            OutputFormat::Text if by_package => {
+                print_by_package(dependencies, recursive, fail_print)
+            }
+            OutputFormat::Text => print_by_license(dependencies, recursive, fail_print),
+        }
+        if output_format == OutputFormat::Text {
+            println!();
+            println!("Found {} total dependencies.", num_dep.to_string().cyan());
+            println!(
+                "Found {} dependencies with licenses to avoid.",
+                num_bad_license.to_string().cyan()
+            );
         }
-        println!();
-        println!("Found {} total dependencies.", num_dep.to_string().cyan());
-        println!(
-            "Found {} dependencies with licenses to avoid.",
-            num_bad_license.to_string().cyan()
-        );
     }
 
     exit(num_bad_license);
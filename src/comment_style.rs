@@ -0,0 +1,108 @@
+use std::collections::HashMap;
+
+/// How a source file writes comments: either a line-comment prefix (`#`,
+/// `//`) repeated on every line, or a block-comment delimiter pair
+/// (`/*` ... `*/`) wrapping the whole header. Exactly one of `line_prefix`/
+/// `block` is set; build one with [`CommentStyle::line`] or [`CommentStyle::block`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CommentStyle {
+    pub line_prefix: Option<String>,
+    pub block: Option<(String, String)>,
+}
+
+impl CommentStyle {
+    pub fn line(prefix: &str) -> Self {
+        CommentStyle {
+            line_prefix: Some(prefix.to_string()),
+            block: None,
+        }
+    }
+
+    pub fn block(start: &str, end: &str) -> Self {
+        CommentStyle {
+            line_prefix: None,
+            block: Some((start.to_string(), end.to_string())),
+        }
+    }
+
+    /// Whether `text` opens a comment in this style, e.g. a line starting
+    /// with `#` or `/*`.
+    pub fn starts_with_comment(&self, text: &str) -> bool {
+        if let Some(prefix) = &self.line_prefix {
+            return text.starts_with(prefix.as_str());
+        }
+        if let Some((start, _)) = &self.block {
+            return text.starts_with(start.as_str());
+        }
+        false
+    }
+
+    /// Whether `line` (ignoring leading whitespace) opens a comment in this style.
+    pub fn is_comment_line(&self, line: &str) -> bool {
+        self.starts_with_comment(line.trim_start())
+    }
+
+    /// Strip this style's comment markers from `line` and trim whitespace, so
+    /// a found header line can be compared word-for-word against a template line.
+    pub fn strip_line(&self, line: &str) -> String {
+        if let Some(prefix) = &self.line_prefix {
+            return line.trim_start_matches(prefix.as_str()).trim().to_string();
+        }
+        if let Some((start, end)) = &self.block {
+            let stripped = line.trim();
+            let stripped = stripped.strip_prefix(start.as_str()).unwrap_or(stripped).trim();
+            let stripped = stripped.strip_suffix(end.as_str()).unwrap_or(stripped).trim();
+            return stripped.to_string();
+        }
+        line.trim().to_string()
+    }
+
+    /// Render `content`'s lines as a comment in this style, the way a filled
+    /// header template is prepared for insertion.
+    pub fn format_block(&self, content: &str) -> String {
+        if let Some(prefix) = &self.line_prefix {
+            return content
+                .lines()
+                .map(|line| {
+                    let line = line.trim();
+                    if line.starts_with(prefix.as_str()) {
+                        line.to_string() + "\n"
+                    } else {
+                        prefix.clone() + " " + line + "\n"
+                    }
+                })
+                .collect();
+        }
+        if let Some((start, end)) = &self.block {
+            let mut rendered = start.clone() + "\n";
+            for line in content.lines() {
+                rendered += line.trim();
+                rendered += "\n";
+            }
+            rendered += end;
+            rendered += "\n";
+            return rendered;
+        }
+        content.to_string()
+    }
+}
+
+/// The built-in extension -> comment-style table, covering common
+/// scripting and systems languages out of the box. `[[comment_styles]]`
+/// entries in the config are layered on top of this in `read_config`.
+pub fn default_styles() -> HashMap<String, CommentStyle> {
+    let mut styles = HashMap::new();
+    for ext in ["py", "sh", "rb", "yaml", "yml", "toml"] {
+        styles.insert(ext.to_string(), CommentStyle::line("#"));
+    }
+    for ext in [
+        "js", "jsx", "ts", "tsx", "go", "rs", "c", "h", "cpp", "hpp", "java", "kt", "swift",
+    ] {
+        styles.insert(ext.to_string(), CommentStyle::line("//"));
+    }
+    styles.insert("css".to_string(), CommentStyle::block("/*", "*/"));
+    for ext in ["html", "htm", "xml", "svg"] {
+        styles.insert(ext.to_string(), CommentStyle::block("<!--", "-->"));
+    }
+    styles
+}
@@ -1,29 +1,266 @@
-use crate::utils::{Config, read_config};
+use crate::argparse::CheckFormat;
+use crate::comment_style::CommentStyle;
+use crate::spdx;
+use crate::utils::{Config, NewlineStyle, read_config};
+use chrono::{Datelike, Utc};
 use colored::Colorize;
 use log::debug;
 use rayon::prelude::*;
 use regex::Regex;
-use std::fs::{DirEntry, File, read_dir};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::fs::{DirEntry, File, read_dir, read_to_string};
 use std::io::{self, BufRead, Read, Seek, SeekFrom, Write};
-use std::path::PathBuf;
-use std::process::exit;
+use std::path::{Path, PathBuf};
+use std::process::{Command, exit};
 
-const COMMENT: &str = "#";
+// The shebang marker is a Unix convention independent of a language's own
+// comment syntax, so it is checked for literally rather than through `CommentStyle`.
 const HASHBANG: &str = "#!";
 
-#[derive(PartialEq, PartialOrd, Debug)]
+// An XML/HTML declaration prologue, e.g. `<?xml version="1.0"?>`, is skipped
+// the same way a shebang is - it must stay the first line of the file, before
+// any header comment.
+const XML_PROLOG: &str = "<?xml";
+
+// A leading UTF-8 byte-order mark some editors prepend to XML/HTML files;
+// stripped from the first line before any other check runs.
+const BOM: char = '\u{FEFF}';
+
+#[derive(PartialEq, PartialOrd, Debug, Clone, Copy)]
 enum LicenseCheckRes {
     Missing,
     Found,
     Outdated,
+    /// Opted out via an inline `licensepy: skip-file` directive. Neither
+    /// reported as `Outdated`/`Missing` nor rewritten.
+    Skipped,
+}
+
+/// Regex matching the inline escape directive that opts a file out of header
+/// enforcement entirely, e.g. `# licensepy: skip-file`. Recognized anywhere
+/// in the file's first comment block, independent of the file's comment style.
+fn skip_directive_re() -> Regex {
+    Regex::new(r"(?i)licensepy:\s*skip-file").unwrap()
 }
 
 pub struct Formatter {
     files: Vec<PathBuf>,
-    header: String,
     config: Config,
+    remove: bool,
     silent: bool,
     dry_run: bool,
+    /// Compiled `config.skip_patterns`, checked once per file in `format_file`
+    /// - distinct from the walk-time `IgnoreMatcher` built in `new`, since an
+    ///   explicitly-passed file bypasses the walk entirely.
+    skip_matcher: IgnoreMatcher,
+    /// Run in `--check` mode: report each file's status through the
+    /// `check_format` `Emitter` and write nothing, instead of inserting/updating/removing headers in place.
+    check: bool,
+    /// The `Emitter` to drive when `check` is set.
+    check_format: CheckFormat,
+}
+
+/// Look up the comment style to use for `file` from `config.comment_styles`
+/// by its extension, falling back to `#` line comments (Python's style, and
+/// the most common default) for files with an unrecognized or missing extension.
+fn style_for(config: &Config, file: &Path) -> CommentStyle {
+    file.extension()
+        .and_then(|ext| ext.to_str())
+        .and_then(|ext| config.comment_styles.get(ext))
+        .cloned()
+        .unwrap_or_else(|| CommentStyle::line("#"))
+}
+
+/// Detect the dominant line ending already present in `content` by counting
+/// `\r\n` occurrences against lone `\n` ones, so `NewlineStyle::Auto` matches
+/// whatever convention the file already uses. Defaults to `"\n"` when the
+/// content has no line endings at all (e.g. an empty or single-line file).
+fn detect_line_ending(content: &str) -> &'static str {
+    let crlf = content.matches("\r\n").count();
+    let lf = content.matches('\n').count();
+    if crlf * 2 > lf { "\r\n" } else { "\n" }
+}
+
+/// Resolve `style` to the concrete line ending the formatter should write
+/// for this file.
+fn resolve_newline_style(style: NewlineStyle, content: &str) -> &'static str {
+    match style {
+        NewlineStyle::Auto => detect_line_ending(content),
+        NewlineStyle::Unix => "\n",
+        NewlineStyle::Windows => "\r\n",
+        NewlineStyle::Native => {
+            if cfg!(windows) {
+                "\r\n"
+            } else {
+                "\n"
+            }
+        }
+    }
+}
+
+/// Rewrite `text` (assumed to use `\n`, as built by `format_header`/`style.format_block`
+/// and `find_first_comment`'s line-joining) to use `line_ending` instead, so it
+/// matches the rest of the file's convention.
+fn to_line_ending(text: &str, line_ending: &str) -> String {
+    if line_ending == "\n" {
+        text.to_string()
+    } else {
+        text.replace('\n', line_ending)
+    }
+}
+
+/// Apply `config.ensure_trailing_newline`/`config.collapse_trailing_blank_lines`
+/// to a fully-assembled file buffer before it's written back, mirroring
+/// rustfmt's `append_newline` - called once per write so every write path
+/// (`insert_header`, `update_header`, `remove_header`) behaves identically.
+fn finalize_content(mut content: String, config: &Config, line_ending: &str) -> String {
+    if content.is_empty() {
+        return content;
+    }
+    if config.collapse_trailing_blank_lines {
+        let trimmed_len = content.trim_end_matches(line_ending).len();
+        content.truncate(trimmed_len);
+        content.push_str(line_ending);
+    } else if config.ensure_trailing_newline && !content.ends_with(line_ending) {
+        content.push_str(line_ending);
+    }
+    content
+}
+
+/// The ignore patterns applied when no files are given as CLI arguments and
+/// the cwd is walked recursively for source files, even if the user config
+/// and `.licensepyignore` supply none of their own.
+const DEFAULT_IGNORE_PATTERNS: [&str; 4] = ["dist/", "__pycache__/", "*.egg-info/", ".*"];
+
+/// The name of the `.licensepyignore` file, if present in the cwd, whose
+/// lines are layered on top of `config.ignore_patterns` the way `.gitignore`
+/// layers on top of a repo's own defaults.
+const IGNORE_FILE: &str = ".licensepyignore";
+
+/// Compile a gitignore-style pattern into a regex matching a candidate
+/// string in full (either a bare file/directory name or a `/`-joined
+/// relative path, depending on whether the pattern itself contains a `/`;
+/// see [`IgnorePattern::compile`]). Supports `*` (any run of non-`/`
+/// characters), `?` (a single non-`/` character), and `**` (any run of
+/// characters, including `/`) - a practical subset of gitignore globbing,
+/// not bracket expressions or escape sequences.
+fn pattern_to_regex(pattern: &str) -> Regex {
+    let mut out = String::from("^");
+    let mut chars = pattern.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '*' if chars.peek() == Some(&'*') => {
+                chars.next();
+                if chars.peek() == Some(&'/') {
+                    chars.next();
+                }
+                out.push_str(".*");
+            }
+            '*' => out.push_str("[^/]*"),
+            '?' => out.push_str("[^/]"),
+            other => out.push_str(&regex::escape(&other.to_string())),
+        }
+    }
+    out.push('$');
+    Regex::new(&out).unwrap_or_else(|err| panic!("Invalid ignore pattern {pattern:?}: {err}"))
+}
+
+/// A single compiled gitignore-style ignore pattern.
+struct IgnorePattern {
+    regex: Regex,
+    /// `!`-prefixed: a later match of this pattern un-ignores a path ignored
+    /// by an earlier one, mirroring gitignore negation.
+    negate: bool,
+    /// Trailing-`/`: only matches directories.
+    dir_only: bool,
+    /// Whether the pattern contains a `/` (besides a trailing one), meaning
+    /// it's matched against the full relative path rather than a bare name.
+    has_slash: bool,
+}
+
+impl IgnorePattern {
+    fn compile(raw: &str) -> Self {
+        let negate = raw.starts_with('!');
+        let raw = raw.strip_prefix('!').unwrap_or(raw);
+        let dir_only = raw.ends_with('/');
+        let body = raw.strip_suffix('/').unwrap_or(raw);
+        let body = body.strip_prefix('/').unwrap_or(body);
+        let has_slash = body.contains('/');
+        IgnorePattern {
+            regex: pattern_to_regex(body),
+            negate,
+            dir_only,
+            has_slash,
+        }
+    }
+}
+
+/// A set of gitignore-style ignore patterns, applied to both directories and
+/// files during the recursive source-file walk, replacing the old fixed
+/// 4-entry regex array. Patterns are layered, in order, from
+/// [`DEFAULT_IGNORE_PATTERNS`], `config.ignore_patterns`, then
+/// `.licensepyignore` if present - later patterns (and `!`-negations) take
+/// precedence, same as `.gitignore`.
+struct IgnoreMatcher {
+    patterns: Vec<IgnorePattern>,
+}
+
+impl IgnoreMatcher {
+    fn new(config: &Config) -> Self {
+        let mut raw_patterns: Vec<String> = DEFAULT_IGNORE_PATTERNS
+            .iter()
+            .map(ToString::to_string)
+            .collect();
+        raw_patterns.extend(config.ignore_patterns.iter().cloned());
+
+        if let Ok(contents) = read_to_string(IGNORE_FILE) {
+            for line in contents.lines() {
+                let line = line.trim();
+                if line.is_empty() || line.starts_with('#') {
+                    continue;
+                }
+                raw_patterns.push(line.to_string());
+            }
+        }
+
+        Self::from_patterns(&raw_patterns)
+    }
+
+    /// Build a matcher from a bare list of gitignore-style patterns, with no
+    /// built-in defaults or `.licensepyignore` layering - used for
+    /// `config.skip_patterns`, which opts files out one at a time rather than
+    /// pruning the recursive walk.
+    fn from_patterns(raw_patterns: &[String]) -> Self {
+        IgnoreMatcher {
+            patterns: raw_patterns.iter().map(|p| IgnorePattern::compile(p)).collect(),
+        }
+    }
+
+    /// Whether `path` (relative to the walk's root) should be skipped.
+    ///
+    /// Args:
+    ///     - path: The relative path of the file or directory, as built up by
+    ///         `find_source_files`.
+    ///     - name: The bare file/directory name, matched against patterns
+    ///         that don't contain a `/`.
+    ///     - is_dir: Whether `path` is a directory, for `dir_only` patterns.
+    fn is_ignored(&self, path: &Path, name: &str, is_dir: bool) -> bool {
+        let full = path.to_string_lossy().replace('\\', "/");
+        let full = full.strip_prefix("./").unwrap_or(&full);
+
+        let mut ignored = false;
+        for pattern in &self.patterns {
+            if pattern.dir_only && !is_dir {
+                continue;
+            }
+            let candidate = if pattern.has_slash { full } else { name };
+            if pattern.regex.is_match(candidate) {
+                ignored = !pattern.negate;
+            }
+        }
+        ignored
+    }
 }
 
 impl Formatter {
@@ -35,28 +272,54 @@ impl Formatter {
     ///
     /// The files to update the license header for are:
     /// - the positional command line arguments if available
-    /// - otherwise, all the python files recursively found under the cwd excluding
-    ///   `*.egg-info/`, dist/, __pycache__/, and hidden directories and files.
+    /// - otherwise, all source files with a recognized extension (see
+    ///   `config.comment_styles`) recursively found under the cwd, skipping
+    ///   anything matched by the gitignore-style patterns built up by
+    ///   [`IgnoreMatcher`] (`*.egg-info/`, dist/, __pycache__/, and hidden
+    ///   directories and files by default).
     ///
     /// Args:
-    ///     - files: Python files to run license header formatter on. If any provided, no
+    ///     - files: Source files to run license header formatter on. If any provided, no
     ///         search for files is run.
     ///     - cl_licensee: The command line input for the `licensee` to use in header template.
     ///     - cl_license_year: The command line input for the `license_year` to use in the header template.
+    ///     - cl_use_current_year: The command line input for forcing `{year}` to the current
+    ///         calendar year, overriding `license_year`.
+    ///     - cl_spdx_mode: The command line input for recognizing/managing SPDX tag lines
+    ///         instead of matching `license_header_template`.
+    ///     - cl_remove: The command line input for running in `remove` mode, which deletes a
+    ///         recognized header instead of inserting or updating one.
     ///     - silent: The command line input for whether to print results of checks and formatting.
     ///     - dry_run: The command line input for whether to only run check for correct license header
     ///         without running formatter.
+    ///     - check: The command line input for running in `--check` mode, which reports
+    ///         compliance through `check_format` and writes nothing.
+    ///     - check_format: The command line input for which `Emitter` drives `--check` output.
     ///
     /// Returns: A Formatter.
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         files: &[String],
         cl_licensee: &Option<String>,
         cl_license_year: &Option<u16>,
+        cl_use_current_year: bool,
+        cl_spdx_mode: bool,
+        cl_remove: bool,
         silent: bool,
         dry_run: bool,
+        check: bool,
+        check_format: CheckFormat,
     ) -> Self {
         let mut config = read_config();
-        if config.license_header_template.is_none() {
+        if cl_spdx_mode {
+            config.spdx_mode = true;
+        }
+        if config.spdx_mode {
+            if config.accepted_spdx_licenses.is_empty() {
+                println!("No accepted_spdx_licenses found in config file for SPDX mode.");
+                exit(1);
+            }
+        } else if config.license_header_template.is_none() {
             println!("No license header found in config file.");
             exit(1);
         }
@@ -71,42 +334,57 @@ impl Formatter {
             config.license_year = i64::from(*year);
         }
 
-        // generate the header from the template and command line arguments
-        let header = format_header(&config);
+        if cl_use_current_year {
+            config.use_current_year = true;
+        }
+        if config.use_current_year {
+            // overrides any config/command-line license_year: the inserted
+            // and checked-against year always tracks the calendar year.
+            config.license_year = i64::from(Utc::now().year());
+        }
 
         // the files to update the license header for are:
         // - the positional command line arguments if available
-        // - otherwise, all the python files recursively found under the cwd excluding
-        //         *.egg-info/, dist/, __pycache__/, and hidden directories and files.
+        // - otherwise, all source files with a recognized extension recursively found
+        //         under the cwd, skipping anything the ignore patterns match.
         let files: Vec<PathBuf> = if !files.is_empty() {
             files
                 .iter()
                 .map(PathBuf::from)
-                .filter(|path| path.exists() && path.extension().unwrap() == "py")
+                .filter(|path| path.exists())
                 .collect()
         } else {
-            let mut python_files: Vec<PathBuf> = vec![];
-            let ignore_dirs: [Regex; 4] = [
-                Regex::new(r"^dist$").unwrap(),
-                Regex::new(r"^__pycache__$").unwrap(),
-                Regex::new(r"^.*\.egg-info$").unwrap(),
-                Regex::new(r"^\..*$").unwrap(),
-            ];
-            find_python_files(PathBuf::from("./"), &mut python_files, &ignore_dirs);
-            python_files
+            let mut source_files: Vec<PathBuf> = vec![];
+            let ignore = IgnoreMatcher::new(&config);
+            find_source_files(
+                PathBuf::from("./"),
+                &mut source_files,
+                &ignore,
+                &config.comment_styles,
+            );
+            source_files
         };
 
+        let skip_matcher = IgnoreMatcher::from_patterns(&config.skip_patterns);
+
         Formatter {
             files,
-            header,
             config,
+            remove: cl_remove,
             silent,
             dry_run,
+            skip_matcher,
+            check,
+            check_format,
         }
     }
 
     /// Run the license header check and formatter on the collected files with multithreading.
     pub fn format_files(&self) {
+        if self.check {
+            return self.check_files();
+        }
+
         // total the number of files that had incorrect license headers.
         let num_to_fix: i32 = self
             .files
@@ -137,29 +415,79 @@ impl Formatter {
             File::options().read(true).write(true).open(file).unwrap()
         };
         let file_path = file.as_path().to_str().unwrap();
+        let file_name = file.file_name().and_then(|n| n.to_str()).unwrap_or("");
+        if self.skip_matcher.is_ignored(file, file_name, false) {
+            if !self.silent {
+                println!(
+                    "{}: Skipped (matches a skip_patterns entry).",
+                    file_path.cyan().bold()
+                );
+            }
+            return false;
+        }
+        let style = style_for(&self.config, file);
+
+        // `use_current_year` always wins (it already overrode config.license_year
+        // in `Formatter::new`); otherwise prefer this file's own last-commit year
+        // over the shared config/CLI year when `use_vcs_year` is enabled.
+        let license_year = if !self.config.use_current_year && self.config.use_vcs_year {
+            vcs_year_for_file(file).unwrap_or(self.config.license_year)
+        } else {
+            self.config.license_year
+        };
+
+        // peek at the file's existing content to resolve the line ending to
+        // write, then rewind so `find_first_comment` reads from the start.
+        let mut peeked_content = String::new();
+        f.read_to_string(&mut peeked_content).unwrap();
+        f.seek(SeekFrom::Start(0)).unwrap();
+        let line_ending = resolve_newline_style(self.config.newline_style, &peeked_content);
 
         // extract the first comment block
-        let (found_header, insert_at) = find_first_comment(&f);
+        let (found_header, insert_at) = find_first_comment(&f, &style, line_ending);
         let mut needs_fix = false;
 
         // run the checker to see if the header is missing, found, or outdated
         // and call appropriate function
-        match check_license(&found_header, &self.config) {
-            (_, LicenseCheckRes::Missing) => {
+        let check_result = if self.config.spdx_mode {
+            check_spdx_header(&found_header, &self.config, license_year)
+        } else {
+            check_license(&found_header, &self.config, &style, license_year)
+        };
+
+        if self.remove {
+            return self.remove_file_header(&mut f, file_path, check_result, line_ending);
+        }
+
+        match check_result {
+            (_, LicenseCheckRes::Missing, _) => {
                 needs_fix = true;
                 if !self.silent {
                     println!("{}: License header missing.", file_path.red().bold());
                 }
                 if !self.dry_run {
-                    insert_header(&mut f, &self.header, insert_at);
+                    let header = if self.config.spdx_mode {
+                        format_spdx_header(&self.config, &style, &license_year.to_string())
+                    } else {
+                        format_header(&self.config, &style, license_year)
+                    };
+                    insert_header(&mut f, &header, insert_at, &style, &self.config, line_ending);
                 }
             }
-            (_, LicenseCheckRes::Found) => {
+            (_, LicenseCheckRes::Found, _) => {
                 if !self.silent {
                     println!("{}: License header found.", file_path.cyan().bold());
                 }
             }
-            (replace, LicenseCheckRes::Outdated) => {
+            (_, LicenseCheckRes::Skipped, _) => {
+                if !self.silent {
+                    println!(
+                        "{}: Skipped (licensepy: skip-file).",
+                        file_path.cyan().bold()
+                    );
+                }
+            }
+            (replace, LicenseCheckRes::Outdated, year_range_start) => {
                 needs_fix = true;
                 if !self.silent {
                     println!(
@@ -170,34 +498,425 @@ impl Formatter {
                 debug!("Found {found_header}");
                 debug!("Replace {replace}");
                 if !self.dry_run {
-                    update_header(&mut f, &replace, &self.header);
+                    // expand the existing start year into a range ending at the
+                    // current license year instead of overwriting it outright,
+                    // e.g. "2015" / "2015-2018" -> "2015-2025".
+                    let year_value = match year_range_start {
+                        Some(start) if start != license_year => {
+                            format!("{start}-{license_year}")
+                        }
+                        _ => license_year.to_string(),
+                    };
+                    let header = if self.config.spdx_mode {
+                        format_spdx_header(&self.config, &style, &year_value)
+                    } else {
+                        format_header_with_year(&self.config, &style, &year_value)
+                    };
+                    update_header(&mut f, &replace, &header, &style, &self.config, line_ending);
                 }
             }
         }
 
         needs_fix
     }
+
+    /// Run `remove` mode on a file: delete a recognized header (`Found` or
+    /// `Outdated`) instead of inserting or updating one, useful when
+    /// migrating between license texts - remove all old headers, update the
+    /// template, then re-run without `remove` to insert fresh ones.
+    ///
+    /// Args:
+    ///     - file: The open file to remove the header from.
+    ///     - file_path: The file's path, for printed output.
+    ///     - check_result: The result of `check_license`/`check_spdx_header` for this file.
+    ///     - line_ending: The file's resolved line ending, for locating the
+    ///         existing header in the file's actual content.
+    ///
+    /// Returns whether the file had a header removed.
+    fn remove_file_header(
+        &self,
+        file: &mut File,
+        file_path: &str,
+        check_result: (String, LicenseCheckRes, Option<i64>),
+        line_ending: &str,
+    ) -> bool {
+        let (existing_header, check_res, _) = check_result;
+        match check_res {
+            LicenseCheckRes::Missing | LicenseCheckRes::Skipped => {
+                if !self.silent {
+                    println!("{}: No license header to remove.", file_path.cyan().bold());
+                }
+                false
+            }
+            LicenseCheckRes::Found | LicenseCheckRes::Outdated => {
+                if !self.silent {
+                    println!("{}: Removing license header.", file_path.red().bold());
+                }
+                if !self.dry_run {
+                    remove_header(file, &existing_header, &self.config, line_ending);
+                }
+                true
+            }
+        }
+    }
+
+    /// Run `--check` mode: evaluate every collected file, drive `check_format`'s
+    /// `Emitter` over the results, and exit with the number of non-compliant
+    /// files without writing to any of them.
+    fn check_files(&self) {
+        let checks: Vec<FileCheck> = self.files.par_iter().map(|file| self.check_file(file)).collect();
+
+        let num_to_fix = checks
+            .iter()
+            .filter(|c| !matches!(c.check_res, LicenseCheckRes::Found | LicenseCheckRes::Skipped))
+            .count() as i32;
+
+        let mut emitter: Box<dyn Emitter> = match self.check_format {
+            CheckFormat::Diff => Box::new(DiffEmitter),
+            CheckFormat::Json => Box::new(JsonEmitter::default()),
+        };
+        emitter.emit_header();
+        for check in &checks {
+            emitter.emit_file(check);
+        }
+        emitter.emit_footer();
+
+        if !self.silent {
+            println!("\n{} files to fix.", num_to_fix.to_string().red().bold());
+        }
+        exit(num_to_fix);
+    }
+
+    /// Evaluate `file` for `--check`: the read-only counterpart to `format_file`,
+    /// reusing the same [`check_license`]/[`check_spdx_header`] checker and
+    /// [`proposed_edit`] edit computation so the two can't disagree about
+    /// whether - or how - a file would be fixed.
+    fn check_file(&self, file: &PathBuf) -> FileCheck {
+        let file_path = file.as_path().to_str().unwrap().to_string();
+        let file_name = file.file_name().and_then(|n| n.to_str()).unwrap_or("");
+        if self.skip_matcher.is_ignored(file, file_name, false) {
+            return FileCheck {
+                file_path,
+                check_res: LicenseCheckRes::Skipped,
+                insert_at: 0,
+                diff: None,
+            };
+        }
+
+        let style = style_for(&self.config, file);
+        let license_year = if !self.config.use_current_year && self.config.use_vcs_year {
+            vcs_year_for_file(file).unwrap_or(self.config.license_year)
+        } else {
+            self.config.license_year
+        };
+
+        let mut f = File::options().read(true).open(file).unwrap();
+        let mut content = String::new();
+        f.read_to_string(&mut content).unwrap();
+        f.seek(SeekFrom::Start(0)).unwrap();
+        let line_ending = resolve_newline_style(self.config.newline_style, &content);
+
+        let (found_header, insert_at) = find_first_comment(&f, &style, line_ending);
+        let (matched, check_res, year_range_start) = if self.config.spdx_mode {
+            check_spdx_header(&found_header, &self.config, license_year)
+        } else {
+            check_license(&found_header, &self.config, &style, license_year)
+        };
+
+        let diff = proposed_edit(
+            check_res,
+            &matched,
+            year_range_start,
+            license_year,
+            &content,
+            insert_at,
+            &style,
+            &self.config,
+            line_ending,
+            self.remove,
+        )
+        .map(|new_content| unified_diff(&content, &new_content, &file_path));
+
+        FileCheck {
+            file_path,
+            check_res,
+            insert_at,
+            diff,
+        }
+    }
+}
+
+/// A file's `--check` result: its path, what the checker found, the byte
+/// offset a missing header would be inserted at, and (when non-compliant) a
+/// unified diff of the edit `format_file` would make. Drives an [`Emitter`].
+struct FileCheck {
+    file_path: String,
+    check_res: LicenseCheckRes,
+    insert_at: usize,
+    diff: Option<String>,
+}
+
+/// Drives `--check` output across a batch of files: one call to frame the
+/// batch before/after, and one call per file as its result comes in. Modeled
+/// on rustfmt's `Emitter` for `--check`/`--emit`.
+trait Emitter {
+    fn emit_header(&mut self) {}
+    fn emit_file(&mut self, check: &FileCheck);
+    fn emit_footer(&mut self) {}
+}
+
+/// Emits a unified `---`/`+++` diff hunk for every non-compliant file, the
+/// default (and only human-readable) `--check` output.
+struct DiffEmitter;
+
+impl Emitter for DiffEmitter {
+    fn emit_file(&mut self, check: &FileCheck) {
+        if let Some(diff) = &check.diff {
+            print!("{diff}");
+        }
+    }
+}
+
+/// The JSON projection of a [`FileCheck`] emitted by `--check --check-format json`.
+#[derive(Serialize)]
+struct JsonEntry {
+    path: String,
+    status: &'static str,
+    insert_offset: usize,
+}
+
+impl From<&FileCheck> for JsonEntry {
+    fn from(check: &FileCheck) -> Self {
+        JsonEntry {
+            path: check.file_path.clone(),
+            status: match check.check_res {
+                LicenseCheckRes::Missing => "Missing",
+                LicenseCheckRes::Found => "OK",
+                LicenseCheckRes::Outdated => "Outdated",
+                LicenseCheckRes::Skipped => "Skipped",
+            },
+            insert_offset: check.insert_at,
+        }
+    }
+}
+
+/// Collects every file's result and prints them as one JSON array on
+/// `emit_footer`, for machine consumption in CI.
+#[derive(Default)]
+struct JsonEmitter {
+    entries: Vec<JsonEntry>,
+}
+
+impl Emitter for JsonEmitter {
+    fn emit_file(&mut self, check: &FileCheck) {
+        self.entries.push(JsonEntry::from(check));
+    }
+
+    fn emit_footer(&mut self) {
+        match serde_json::to_string_pretty(&self.entries) {
+            Ok(json) => println!("{json}"),
+            Err(err) => eprintln!("Failed to serialize check results to JSON: {err}"),
+        }
+    }
+}
+
+/// Determine the last-modified year for `file` from its git history
+/// (`git log -1 --date=format:%Y`), used for `config.use_vcs_year` so a repo
+/// spanning many years gets a correct per-file `{year}` in a single pass.
+///
+/// Returns `None` - so callers fall back to `config.license_year` - when git
+/// isn't available, `file` has no commits (e.g. it's new or untracked), or
+/// the output can't be parsed.
+fn vcs_year_for_file(file: &Path) -> Option<i64> {
+    let output = Command::new("git")
+        .args(["log", "-1", "--format=%ad", "--date=format:%Y", "--"])
+        .arg(file)
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    String::from_utf8(output.stdout).ok()?.trim().parse().ok()
+}
+
+/// Compile a filled header template into a regex that matches it verbatim:
+/// literal text is regex-escaped, `{{`/`}}` become literal braces, and each
+/// `{pattern}` is emitted as a capture group holding `pattern` as a raw
+/// sub-regex - e.g. `Copyright {\d{4}(-\d{4})?} {.+ Inc\.}` tolerates year
+/// ranges and variable company suffixes. The reserved `{year}` placeholder is
+/// special-cased to the named groups `year_start`/`year_end`, so existing
+/// templates keep matching a bare `2015` or a `2015-2025` range as before.
+/// The whole pattern is anchored (`^...$`) so a window must match in full.
+fn compile_template_regex(template: &str) -> Regex {
+    let mut pattern = String::from("(?s)^");
+    let mut chars = template.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '{' if chars.peek() == Some(&'{') => {
+                chars.next();
+                pattern.push_str(&regex::escape("{"));
+            }
+            '}' if chars.peek() == Some(&'}') => {
+                chars.next();
+                pattern.push_str(&regex::escape("}"));
+            }
+            '{' => {
+                // track nesting so a sub-regex's own `{4}` repetition braces
+                // stay inside the placeholder.
+                let mut inner = String::new();
+                let mut depth = 0usize;
+                for c2 in chars.by_ref() {
+                    match c2 {
+                        '{' => depth += 1,
+                        '}' if depth == 0 => break,
+                        '}' => depth -= 1,
+                        _ => {}
+                    }
+                    inner.push(c2);
+                }
+                if inner == "year" {
+                    pattern.push_str(r"(?P<year_start>\d{4})(?:-(?P<year_end>\d{4}))?");
+                } else {
+                    pattern.push('(');
+                    pattern.push_str(&inner);
+                    pattern.push(')');
+                }
+            }
+            other => pattern.push_str(&regex::escape(&other.to_string())),
+        }
+    }
+    pattern.push('$');
+    Regex::new(&pattern).unwrap_or_else(|err| panic!("Invalid license_header_template: {err}"))
+}
+
+/// Normalized Levenshtein similarity ratio between two whitespace-tokenized
+/// strings: `1 - edit_distance / max(len_a, len_b)`, computed with a standard
+/// dynamic-programming edit-distance table over tokens. `1.0` means identical,
+/// `0.0` means completely different.
+fn levenshtein_ratio(a: &str, b: &str) -> f64 {
+    let a_tokens: Vec<&str> = a.split_whitespace().collect();
+    let b_tokens: Vec<&str> = b.split_whitespace().collect();
+    let len_a = a_tokens.len();
+    let len_b = b_tokens.len();
+
+    if len_a == 0 && len_b == 0 {
+        return 1.0;
+    }
+
+    let mut dp = vec![vec![0usize; len_b + 1]; len_a + 1];
+    for (i, row) in dp.iter_mut().enumerate() {
+        row[0] = i;
+    }
+    for j in 0..=len_b {
+        dp[0][j] = j;
+    }
+    for i in 1..=len_a {
+        for j in 1..=len_b {
+            let cost = usize::from(a_tokens[i - 1] != b_tokens[j - 1]);
+            dp[i][j] = (dp[i - 1][j] + 1)
+                .min(dp[i][j - 1] + 1)
+                .min(dp[i - 1][j - 1] + cost);
+        }
+    }
+
+    1.0 - (dp[len_a][len_b] as f64) / (len_a.max(len_b) as f64)
+}
+
+/// Find the first bare year or year range (e.g. `2015` or `2015-2025`)
+/// anywhere in `text` and return its byte span and parsed start/end years.
+/// Used by [`check_license`]'s fuzzy fallback to exclude the year from the
+/// similarity comparison - so year drift alone doesn't lower the score -
+/// while still recovering it to preserve a `{year}` range when the header is
+/// rewritten.
+fn find_year_token(text: &str) -> Option<(usize, usize, i64, i64)> {
+    let re = Regex::new(r"(\d{4})(?:-(\d{4}))?").unwrap();
+    let caps = re.captures(text)?;
+    let whole = caps.get(0).unwrap();
+    let start: i64 = caps[1].parse().unwrap();
+    let end: i64 = caps
+        .get(2)
+        .map(|m| m.as_str().parse().unwrap())
+        .unwrap_or(start);
+    Some((whole.start(), whole.end(), start, end))
+}
+
+/// Strip `{pattern}` placeholders (keeping literal doubled `{{`/`}}` braces)
+/// from a filled template, the same way [`compile_template_regex`] parses
+/// them, leaving just the template's literal skeleton text for the fuzzy
+/// fallback in [`check_license`] to compare against.
+fn strip_placeholders(template: &str) -> String {
+    let mut result = String::new();
+    let mut chars = template.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '{' if chars.peek() == Some(&'{') => {
+                chars.next();
+                result.push('{');
+            }
+            '}' if chars.peek() == Some(&'}') => {
+                chars.next();
+                result.push('}');
+            }
+            '{' => {
+                let mut depth = 0usize;
+                for c2 in chars.by_ref() {
+                    match c2 {
+                        '{' => depth += 1,
+                        '}' if depth == 0 => break,
+                        '}' => depth -= 1,
+                        _ => {}
+                    }
+                }
+            }
+            other => result.push(other),
+        }
+    }
+    result
 }
 
-/// Check if the found comment block is a valid license header.
+/// Check if the found comment block is a valid license header by matching it
+/// against the template compiled to a regex by [`compile_template_regex`].
+///
+/// Slides a window the size of the (line-count of the) filled template across
+/// the found comment block - the block may have unrelated comment lines
+/// before or after the real header - since the regex is anchored to a whole
+/// window rather than searched for within it. The first window that matches
+/// decides the result: if the year the template's `{year}` placeholder matched
+/// (or, for a year range, its end year) equals `license_year`, it's `Found`;
+/// otherwise it's `Outdated`.
+///
+/// If no window matches the regex exactly, falls back to a fuzzy pass so a
+/// header with a renamed licensee, reflowed wrapping, or one stray word
+/// outside a `{pattern}` capture is repaired in place instead of being
+/// treated as missing and getting a duplicate header stacked on top: every
+/// window is scored by the normalized Levenshtein similarity ratio between
+/// its text and the template's literal skeleton (placeholders stripped,
+/// and the window's own year token excluded so year drift is scored by the
+/// exact pass above, not this one). The best-scoring window is accepted as
+/// `Outdated` if its ratio clears `config.fuzzy_header_threshold`; otherwise
+/// the header is `Missing`.
 ///
 /// Args:
-///     - comment_block: The first comment block in a Python file.
+///     - comment_block: The first comment block in a source file.
 ///     - config: The config for the formatter.
+///     - style: The comment style of the file the block was found in.
+///     - license_year: The year the header's `{year}` is checked against for
+///         this file (`config.license_year`, or this file's own VCS year
+///         when `config.use_vcs_year` is set).
 ///
-/// Returns: The result of the check LicenseCheckRes::{Missing, Outdated, Found}.
-///
-fn check_license(comment_block: &str, config: &Config) -> (String, LicenseCheckRes) {
-    // Clean license headers by removing # from the beginning and trimming whitespaces
-    let clean_header = |lines: &str| {
-        lines
-            .lines()
-            .map(|line| line.trim_start_matches(COMMENT).trim().to_string())
-            // .filter(|line| !line.is_empty())
-            .collect::<Vec<String>>()
-    };
-    // keep track if the year in the license header is outdated.
-    let mut outdated = false;
+/// Returns: The result of the check LicenseCheckRes::{Missing, Outdated, Found, Skipped}, the
+///     matched header text to replace when outdated, and the original start year of a
+///     `{year}` range found in the header (used to preserve it when the header is updated).
+fn check_license(
+    comment_block: &str,
+    config: &Config,
+    style: &CommentStyle,
+    license_year: i64,
+) -> (String, LicenseCheckRes, Option<i64>) {
+    if skip_directive_re().is_match(comment_block) {
+        return (String::new(), LicenseCheckRes::Skipped, None);
+    }
+
     let mut header_template = config.license_header_template.clone().unwrap();
 
     // By this point we have made sure that the licensee field of the config
@@ -206,133 +925,226 @@ fn check_license(comment_block: &str, config: &Config) -> (String, LicenseCheckR
         header_template = header_template.replace("{licensee}", licensee);
     }
 
-    // clean both the license headers
-    let comments = clean_header(comment_block);
-    let templates = clean_header(&header_template);
+    // Clean the template the same way the found header is cleaned - by
+    // removing comment markers per line - so both sides compare on equal
+    // footing and the regex doesn't have to account for the comment style.
+    let clean_template = header_template
+        .lines()
+        .map(|line| style.strip_line(line))
+        .collect::<Vec<String>>()
+        .join("\n");
+    let template_num_lines = header_template.lines().count();
 
     debug!("Found header {comment_block} expected {header_template}.");
 
-    // If the length of the cleaned headers are different, then the headers are different
-    let template_num_lines = templates.len();
-    if comments.len() < template_num_lines {
+    let raw_lines = comment_block.lines().collect::<Vec<&str>>();
+    let clean_lines: Vec<String> = raw_lines.iter().map(|line| style.strip_line(line)).collect();
+
+    if template_num_lines == 0 || clean_lines.len() < template_num_lines {
         debug!(
             "The found header's length {} is less than the templates {}.",
-            comments.len(),
+            clean_lines.len(),
             template_num_lines,
         );
-        return (String::from(""), LicenseCheckRes::Missing);
+        return (String::from(""), LicenseCheckRes::Missing, None);
     }
 
-    let mut template_line_num = 0usize;
-    let mut cur_template_line = &templates[template_line_num];
-    let mut found_license_start = 0usize;
-
-    for (idx, comment_line) in comments.iter().enumerate() {
-        let comment_words = comment_line.split(" ").collect::<Vec<_>>();
-        let template_words = cur_template_line.split(" ").collect::<Vec<_>>();
+    let re = compile_template_regex(&clean_template);
 
-        if comment_words.len() != template_words.len() {
-            debug!("Length of line {comment_words:?} not equal to {template_words:?}.");
-            // already had found lines of the correct header matching
-            // but matching failed for this line
-            if template_line_num != 0 {
-                return (String::from(""), LicenseCheckRes::Missing);
-            }
+    // slide a window the size of the template across the found comment block
+    // and take the first one that matches the (anchored) template regex.
+    for start in 0..=(clean_lines.len() - template_num_lines) {
+        let window = clean_lines[start..start + template_num_lines].join("\n");
+        let Some(caps) = re.captures(&window) else {
             continue;
-        }
-        let mut matched_words = 0usize;
-        for (comment_word, template_word) in comment_words.into_iter().zip(&template_words) {
-            match template_word.to_string().as_str() {
-                "{year}" => {
-                    // check if the {year} template placeholder matches with a number in
-                    // the comment block
-                    if let Ok(year) = comment_word.parse::<i64>() {
-                        // if a number, check if the year is the same as the year provided in
-                        // in the config. It is outdated if not the same as the license year
-                        // from the config.
-                        if year != config.license_year {
-                            outdated = true;
-                        }
-                    } else {
-                        // if parsing fails, then the headers are different.
-                        debug!("Failed to parse year.");
-                        // already had found lines of the correct header matching
-                        // but matching failed for this line
-                        if template_line_num != 0 {
-                            return (String::from(""), LicenseCheckRes::Missing);
-                        }
-                        continue;
-                    }
-                }
-                word => {
-                    // if the words are different then the headers are different.
-                    if comment_word != word {
-                        debug!("Different words comment {comment_word} template {word}.");
-                        // already had found lines of the correct header matching
-                        // but matching failed for this line
-                        if template_line_num != 0 {
-                            return (String::from(""), LicenseCheckRes::Missing);
-                        }
-                        continue;
-                    }
-                }
+        };
+        debug!("Window at {start} matched.");
+
+        let year_range_start = caps
+            .name("year_start")
+            .map(|m| m.as_str().parse::<i64>().unwrap());
+        let end_year = caps
+            .name("year_end")
+            .or_else(|| caps.name("year_start"))
+            .map(|m| m.as_str().parse::<i64>().unwrap());
+
+        // the matched header text is returned for Found as well as Outdated -
+        // `remove` mode needs it to delete a header that's already correct.
+        let found_header = raw_lines[start..start + template_num_lines].join("\n");
+        return match end_year {
+            Some(end) if end != license_year => {
+                (found_header, LicenseCheckRes::Outdated, year_range_start)
             }
-            matched_words += 1;
+            _ => (found_header, LicenseCheckRes::Found, None),
+        };
+    }
+
+    // No window matched the template exactly. Fall back to a fuzzy pass
+    // instead of reporting Missing outright, so a header with a renamed
+    // licensee, reflowed wrapping, or one stray word isn't treated as
+    // missing and given a duplicate header stacked on top of it.
+    let template_skeleton = strip_placeholders(&clean_template);
+    let mut best_ratio = -1.0;
+    let mut best_start = 0usize;
+    let mut best_year_range_start: Option<i64> = None;
+    for start in 0..=(clean_lines.len() - template_num_lines) {
+        let window = clean_lines[start..start + template_num_lines].join("\n");
+        let mut window_skeleton = window.clone();
+        let year_range_start = find_year_token(&window).map(|(span_start, span_end, year, _)| {
+            window_skeleton.replace_range(span_start..span_end, "");
+            year
+        });
+
+        let ratio = levenshtein_ratio(&template_skeleton, &window_skeleton);
+        if ratio > best_ratio {
+            best_ratio = ratio;
+            best_start = start;
+            best_year_range_start = year_range_start;
         }
-        if template_line_num == 0 {
-            // This is the line where we started matching the header correctly
-            found_license_start = idx;
+    }
+
+    if best_ratio >= config.fuzzy_header_threshold {
+        debug!("Window at {best_start} fuzzy-matched with ratio {best_ratio}.");
+        let found_header = raw_lines[best_start..best_start + template_num_lines].join("\n");
+        return (found_header, LicenseCheckRes::Outdated, best_year_range_start);
+    }
+
+    (String::from(""), LicenseCheckRes::Missing, None)
+}
+
+/// Check a comment block for a `SPDX-FileCopyrightText:`/`SPDX-License-Identifier:`
+/// tag pair, the `spdx_mode` counterpart to [`check_license`]. Only the leading
+/// ~1KB of the block is scanned, matching where these tags are expected to sit.
+///
+/// Args:
+///     - comment_block: The first comment block in a source file.
+///     - config: The config for the formatter; `accepted_spdx_licenses` validates
+///         the found identifier.
+///     - license_year: The year the `SPDX-FileCopyrightText:` tag is checked
+///         against for this file.
+///
+/// Returns: The result of the check LicenseCheckRes::{Missing, Outdated, Found, Skipped}, the
+///     matched tag lines to replace when outdated, and the copyright start year.
+fn check_spdx_header(
+    comment_block: &str,
+    config: &Config,
+    license_year: i64,
+) -> (String, LicenseCheckRes, Option<i64>) {
+    if skip_directive_re().is_match(comment_block) {
+        return (String::new(), LicenseCheckRes::Skipped, None);
+    }
+
+    let copyright_re = Regex::new(r"SPDX-FileCopyrightText:\s*(\d{4})(?:-(\d{4}))?").unwrap();
+    // capture the whole expression (e.g. `MIT OR Apache-2.0`), minus any
+    // trailing block-comment closer on the same line.
+    let license_re =
+        Regex::new(r"SPDX-License-Identifier:\s*(.+?)\s*(?:\*/|-->)?\s*$").unwrap();
+
+    let mut copyright: Option<(usize, i64, i64)> = None;
+    let mut license: Option<(usize, String)> = None;
+    let mut scanned_bytes = 0usize;
+
+    let lines: Vec<&str> = comment_block.lines().collect();
+    for (idx, line) in lines.iter().enumerate() {
+        if scanned_bytes > 1024 {
+            break;
         }
+        scanned_bytes += line.len() + 1;
 
-        if matched_words == template_words.len() {
-            template_line_num += 1;
+        if copyright.is_none()
+            && let Some(caps) = copyright_re.captures(line)
+        {
+            let start: i64 = caps[1].parse().unwrap();
+            let end: i64 = caps
+                .get(2)
+                .map(|m| m.as_str().parse().unwrap())
+                .unwrap_or(start);
+            copyright = Some((idx, start, end));
         }
-        if template_line_num != template_num_lines {
-            cur_template_line = &templates[template_line_num];
-        } else {
-            break;
+        if license.is_none()
+            && let Some(caps) = license_re.captures(line)
+        {
+            license = Some((idx, caps[1].to_string()));
         }
     }
 
-    if template_line_num == template_num_lines {
-        if outdated {
-            let found_license_end = found_license_start + template_line_num;
-            let found_header: String = comment_block.lines().collect::<Vec<&str>>()
-                [found_license_start..found_license_end]
-                .join("\n");
-            return (found_header, LicenseCheckRes::Outdated);
-        } else {
-            return (String::from(""), LicenseCheckRes::Found);
-        }
+    let (Some((copyright_idx, start, end)), Some((license_idx, identifier))) = (copyright, license)
+    else {
+        return (String::from(""), LicenseCheckRes::Missing, None);
+    };
+
+    let valid = spdx::parse_expression(&identifier).is_some()
+        && (config.accepted_spdx_licenses.is_empty()
+            || config.accepted_spdx_licenses.contains(&identifier));
+    if !valid {
+        return (String::from(""), LicenseCheckRes::Missing, None);
     }
-    (String::from(""), LicenseCheckRes::Missing)
+
+    // the matched tag lines are returned for Found as well as Outdated -
+    // `remove` mode needs them to delete a header that's already correct.
+    let block_start = copyright_idx.min(license_idx);
+    let block_end = copyright_idx.max(license_idx);
+    let found_header = lines[block_start..=block_end].join("\n");
+
+    if end != license_year {
+        return (found_header, LicenseCheckRes::Outdated, Some(start));
+    }
+
+    (found_header, LicenseCheckRes::Found, None)
+}
+
+/// Render the canonical two-line SPDX tag block for `spdx_mode`, the
+/// counterpart to [`format_header_with_year`].
+fn format_spdx_header(config: &Config, style: &CommentStyle, year_value: &str) -> String {
+    let identifier = config.accepted_spdx_licenses.first().map_or("", String::as_str);
+    let licensee = config.licensee.as_deref().unwrap_or("");
+    let copyright_line = format!("SPDX-FileCopyrightText: {year_value} {licensee}");
+    let content = format!(
+        "{}\nSPDX-License-Identifier: {identifier}",
+        copyright_line.trim_end()
+    );
+    style.format_block(&content)
 }
 
-/// Find the first comment block for a Python file and the byte index to potentially insert a
-/// license header at. Skip hashbangs, and empty lines before the first none empty line.
+/// Find the first comment block for a source file and the byte index to potentially insert a
+/// license header at. Skips a leading BOM, a hashbang or XML declaration prologue, and empty
+/// lines before the first none empty line.
 ///
 /// Args:
-///     - file: The Python file.
+///     - file: The source file.
+///     - style: The comment style to recognize comment lines with.
+///     - line_ending: The file's resolved line ending, used to count the real
+///         byte length of a skipped line (`BufRead::lines` strips it).
 ///
 /// Returns: The first comment block and the position
-fn find_first_comment(file: &File) -> (String, usize) {
+fn find_first_comment(file: &File, style: &CommentStyle, line_ending: &str) -> (String, usize) {
     // will be used to build the comment block
     let mut found_header: String = String::new();
     // the byte index in the file where a new license header should be inserted
     let mut insert_at: usize = 0;
 
-    for line in io::BufReader::new(file).lines().map_while(|line| line.ok()) {
+    for mut line in io::BufReader::new(file).lines().map_while(|line| line.ok()) {
+        // a BOM can only appear at the very start of the file, so this is a
+        // no-op once `insert_at` has advanced past the first line.
+        if insert_at == 0
+            && found_header.is_empty()
+            && let Some(stripped) = line.strip_prefix(BOM)
+        {
+            insert_at += line.len() - stripped.len();
+            line = stripped.to_string();
+        }
+
         // haven't found a comment yet
         if found_header.is_empty() {
-            if line.starts_with(HASHBANG) {
+            if line.starts_with(HASHBANG) || line.starts_with(XML_PROLOG) {
                 // TODO: Maybe ignore if not the first line of the file
-                // skip hash bang
-                insert_at += line.len() + 1;
+                // skip hash bang / XML declaration
+                insert_at += line.len() + line_ending.len();
                 continue;
             } else if line.trim().is_empty() {
                 // line only contains whitespaces
                 continue;
-            } else if line.starts_with(COMMENT) {
+            } else if style.is_comment_line(&line) {
                 // the first comment line.
                 // don't increment insert_at. If this comment ends up being an
                 // incorrect header, the correct header is inserted before it.
@@ -342,7 +1154,7 @@ fn find_first_comment(file: &File) -> (String, usize) {
                 break;
             }
         // the first comment
-        } else if line.starts_with(COMMENT) {
+        } else if style.is_comment_line(&line) {
             found_header += &line;
             found_header += "\n";
         // first none comment line
@@ -354,68 +1166,79 @@ fn find_first_comment(file: &File) -> (String, usize) {
     (found_header, insert_at)
 }
 
-/// Inserts a license header into a file.
-///
-/// Args:
-///     - file: The Python file in which the license_header is being inserted.
-///     - license_header: The license header being inserted.
-///     - insert_at: The byte index in the file where the license header will be
-///         inserted.
-///
-fn insert_header(file: &mut File, license_header: &str, insert_at: usize) {
-    // The content of the file
-    let mut content = String::new();
-
-    // move cursor to begining and read all the content
-    file.seek(SeekFrom::Start(0)).unwrap();
-    file.read_to_string(&mut content)
-        .expect("Failed to read file");
-    file.set_len(0).unwrap();
-    // move cursor to begining again to avoid strange writing
-    file.seek(SeekFrom::Start(0)).unwrap();
+/// Compute the content `insert_header` would write, without touching a file -
+/// the pure half of `insert_header`, shared with `--check`'s diff emitter so
+/// the two can never disagree about the edit.
+fn compute_insert_edit(
+    content: &str,
+    license_header: &str,
+    insert_at: usize,
+    style: &CommentStyle,
+    config: &Config,
+    line_ending: &str,
+) -> String {
+    let license_header = to_line_ending(license_header, line_ending);
+    let mut out = String::new();
 
     if insert_at == 0 {
         // inserting at the beginning involves writing the header then the rest
         // of the content.
-        file.write_all(license_header.as_bytes()).unwrap();
-        if content.chars().next() == COMMENT.chars().next() {
-            // if the first character of the file is a comment,
+        out.push_str(&license_header);
+        if style.starts_with_comment(content) {
+            // if the file starts with a comment,
             // then add a new line before writing the original content.
-            file.write_all("\n".as_bytes()).unwrap();
+            out.push_str(line_ending);
         }
-        file.write_all(content.as_bytes()).unwrap();
+        out.push_str(content);
     } else {
         // inserting elsewhere involves
         //  - splitting the content at the insert_at byte index,
         //  - writing the first half of the content,
         //  - writing the correct header,
         //  - writing the remainder of the header,
+        let mut content = content.to_string();
         let after_header = content.split_off(insert_at);
 
         // if the content before the header would have been whitespaces,
         // don't write it.
         if !content.trim().is_empty() {
-            file.write_all(content.as_bytes()).unwrap();
+            out.push_str(&content);
         }
 
-        file.write_all(license_header.as_bytes()).unwrap();
-        if after_header.chars().next() == COMMENT.chars().next() {
-            // if the first character of the remaining content is a comment,
+        out.push_str(&license_header);
+        if style.starts_with_comment(&after_header) {
+            // if the remaining content starts with a comment,
             // then add a new line before writing the original content.
-            file.write_all("\n".as_bytes()).unwrap();
+            out.push_str(line_ending);
         }
-        file.write_all(after_header.as_bytes()).unwrap();
+        out.push_str(&after_header);
     }
+
+    finalize_content(out, config, line_ending)
 }
 
-/// Updates a license header in a file with the correct one.
+/// Inserts a license header into a file.
 ///
 /// Args:
-///     - file: The Python file in which the license_header is being inserted.
-///     - existing_header: The existing header in the file.
+///     - file: The file in which the license_header is being inserted.
 ///     - license_header: The license header being inserted.
+///     - insert_at: The byte index in the file where the license header will be
+///         inserted.
+///     - style: The comment style of the file, used to decide whether a blank
+///         line is needed between the inserted header and existing content.
+///     - config: Used for `ensure_trailing_newline`/`collapse_trailing_blank_lines`.
+///     - line_ending: The file's resolved line ending; `license_header` (built
+///         with `\n`) is rewritten to use it before writing.
 ///
-fn update_header(file: &mut File, exisiting_header: &str, license_header: &str) {
+fn insert_header(
+    file: &mut File,
+    license_header: &str,
+    insert_at: usize,
+    style: &CommentStyle,
+    config: &Config,
+    line_ending: &str,
+) {
+    // The content of the file
     let mut content = String::new();
 
     // move cursor to begining and read all the content
@@ -425,29 +1248,240 @@ fn update_header(file: &mut File, exisiting_header: &str, license_header: &str)
     file.set_len(0).unwrap();
     // move cursor to begining again to avoid strange writing
     file.seek(SeekFrom::Start(0)).unwrap();
-    if !content.ends_with('\n') {
+
+    let out = compute_insert_edit(&content, license_header, insert_at, style, config, line_ending);
+    file.write_all(out.as_bytes()).unwrap();
+}
+
+/// Compute the content `update_header` would write, without touching a file -
+/// the pure half of `update_header`, shared with `--check`'s diff emitter.
+fn compute_update_edit(
+    content: &str,
+    exisiting_header: &str,
+    license_header: &str,
+    style: &CommentStyle,
+    config: &Config,
+    line_ending: &str,
+) -> String {
+    let exisiting_header = to_line_ending(exisiting_header, line_ending);
+    let license_header = to_line_ending(license_header, line_ending);
+
+    let mut content = content.to_string();
+    if !content.ends_with(line_ending) {
         debug!("Add new line to end of content");
-        content += "\n";
+        content += line_ending;
     }
 
-    let split_at_idx = content.find(exisiting_header).unwrap();
+    let split_at_idx = content.find(&exisiting_header).unwrap();
     // split the content at the start of the header
     let (before_header, after_header_inclusive) = content.split_at_checked(split_at_idx).unwrap();
 
-    // Write the content before the header, the new header, then the content after the original header.
-    file.write_all(before_header.as_bytes()).unwrap();
+    // Build the content before the header, the new header, then the content after the original header.
+    let mut out = String::new();
+    out.push_str(before_header);
     debug!("License header: <{license_header}>");
-    file.write_all(license_header.as_bytes()).unwrap();
+    out.push_str(&license_header);
 
     // remove the existing header from the content
     let after_header = &after_header_inclusive[exisiting_header.len()..];
     debug!("After header: <{after_header}>");
     debug!("After header inclusive: <{after_header_inclusive}>");
 
-    if after_header.chars().next() == COMMENT.chars().next() {
-        file.write_all("\n".as_bytes()).unwrap();
+    if style.starts_with_comment(after_header) {
+        out.push_str(line_ending);
+    }
+    out.push_str(after_header);
+
+    finalize_content(out, config, line_ending)
+}
+
+/// Updates a license header in a file with the correct one.
+///
+/// Args:
+///     - file: The file in which the license_header is being inserted.
+///     - existing_header: The existing header in the file.
+///     - license_header: The license header being inserted.
+///     - style: The comment style of the file, used to decide whether a blank
+///         line is needed after the replaced header.
+///     - config: Used for `ensure_trailing_newline`/`collapse_trailing_blank_lines`.
+///     - line_ending: The file's resolved line ending; `existing_header` and
+///         `license_header` (both built with `\n`) are rewritten to use it
+///         before searching/writing, since the file's own content may be CRLF.
+///
+fn update_header(
+    file: &mut File,
+    exisiting_header: &str,
+    license_header: &str,
+    style: &CommentStyle,
+    config: &Config,
+    line_ending: &str,
+) {
+    let mut content = String::new();
+
+    // move cursor to begining and read all the content
+    file.seek(SeekFrom::Start(0)).unwrap();
+    file.read_to_string(&mut content)
+        .expect("Failed to read file");
+    file.set_len(0).unwrap();
+    // move cursor to begining again to avoid strange writing
+    file.seek(SeekFrom::Start(0)).unwrap();
+
+    let out = compute_update_edit(
+        &content,
+        exisiting_header,
+        license_header,
+        style,
+        config,
+        line_ending,
+    );
+    file.write_all(out.as_bytes()).unwrap();
+}
+
+/// Compute the content `remove_header` would write, without touching a file -
+/// the pure half of `remove_header`, shared with `--check`'s diff emitter.
+fn compute_remove_edit(content: &str, existing_header: &str, config: &Config, line_ending: &str) -> String {
+    let existing_header = to_line_ending(existing_header, line_ending);
+    let mut content = content.to_string();
+
+    if let Some(idx) = content.find(&existing_header) {
+        content.replace_range(idx..idx + existing_header.len(), "");
+    }
+
+    finalize_content(content, config, line_ending)
+}
+
+/// Deletes a recognized license header from a file, leaving any hashbang
+/// and the rest of the content intact - the `remove`-mode counterpart to
+/// `insert_header`/`update_header`.
+///
+/// Args:
+///     - file: The file the header is being removed from.
+///     - existing_header: The header text to delete, as matched by
+///         `check_license`/`check_spdx_header`.
+///     - config: Used for `ensure_trailing_newline`/`collapse_trailing_blank_lines`.
+///     - line_ending: The file's resolved line ending; `existing_header` (built
+///         with `\n`) is rewritten to use it before searching the file's content.
+fn remove_header(file: &mut File, existing_header: &str, config: &Config, line_ending: &str) {
+    let mut content = String::new();
+
+    // move cursor to begining and read all the content
+    file.seek(SeekFrom::Start(0)).unwrap();
+    file.read_to_string(&mut content)
+        .expect("Failed to read file");
+    file.set_len(0).unwrap();
+    // move cursor to begining again to avoid strange writing
+    file.seek(SeekFrom::Start(0)).unwrap();
+
+    let out = compute_remove_edit(&content, existing_header, config, line_ending);
+    file.write_all(out.as_bytes()).unwrap();
+}
+
+/// Compute the edit `format_file` would make for a file whose checker result
+/// was `check_res`, given the already-read `content` and `insert_at` offset
+/// `find_first_comment` produced - `None` when the file is already compliant
+/// (`Found`/`Skipped`) and there is nothing to do. Shared by the in-place
+/// rewrite (via `insert_header`/`update_header`/`remove_header`, which apply
+/// the equivalent `compute_*_edit` directly) and `--check`, which only diffs it.
+#[allow(clippy::too_many_arguments)]
+fn proposed_edit(
+    check_res: LicenseCheckRes,
+    matched: &str,
+    year_range_start: Option<i64>,
+    license_year: i64,
+    content: &str,
+    insert_at: usize,
+    style: &CommentStyle,
+    config: &Config,
+    line_ending: &str,
+    remove: bool,
+) -> Option<String> {
+    if remove {
+        return match check_res {
+            LicenseCheckRes::Found | LicenseCheckRes::Outdated => {
+                Some(compute_remove_edit(content, matched, config, line_ending))
+            }
+            LicenseCheckRes::Missing | LicenseCheckRes::Skipped => None,
+        };
+    }
+
+    match check_res {
+        LicenseCheckRes::Missing => {
+            let header = if config.spdx_mode {
+                format_spdx_header(config, style, &license_year.to_string())
+            } else {
+                format_header(config, style, license_year)
+            };
+            Some(compute_insert_edit(content, &header, insert_at, style, config, line_ending))
+        }
+        LicenseCheckRes::Outdated => {
+            // expand the existing start year into a range ending at the
+            // current license year instead of overwriting it outright,
+            // e.g. "2015" / "2015-2018" -> "2015-2025".
+            let year_value = match year_range_start {
+                Some(start) if start != license_year => format!("{start}-{license_year}"),
+                _ => license_year.to_string(),
+            };
+            let header = if config.spdx_mode {
+                format_spdx_header(config, style, &year_value)
+            } else {
+                format_header_with_year(config, style, &year_value)
+            };
+            Some(compute_update_edit(content, matched, &header, style, config, line_ending))
+        }
+        LicenseCheckRes::Found | LicenseCheckRes::Skipped => None,
     }
-    file.write_all(after_header.as_bytes()).unwrap();
+}
+
+/// Render a unified diff of `old` -> `new` as a `---`/`+++` hunk, the way
+/// `diff -u` would for `format_file`'s edit - the header/blank-line shuffling
+/// `insert_header`/`update_header` do always lands in one contiguous run of
+/// lines, so a simple common-prefix/common-suffix split is enough; no
+/// general-purpose LCS diff algorithm is needed.
+fn unified_diff(old: &str, new: &str, file_path: &str) -> String {
+    let old_lines: Vec<&str> = old.split_inclusive('\n').collect();
+    let new_lines: Vec<&str> = new.split_inclusive('\n').collect();
+
+    let mut prefix = 0;
+    while prefix < old_lines.len() && prefix < new_lines.len() && old_lines[prefix] == new_lines[prefix] {
+        prefix += 1;
+    }
+
+    let mut suffix = 0;
+    while suffix < old_lines.len() - prefix
+        && suffix < new_lines.len() - prefix
+        && old_lines[old_lines.len() - 1 - suffix] == new_lines[new_lines.len() - 1 - suffix]
+    {
+        suffix += 1;
+    }
+
+    let old_changed = &old_lines[prefix..old_lines.len() - suffix];
+    let new_changed = &new_lines[prefix..new_lines.len() - suffix];
+
+    let mut hunk = String::new();
+    hunk.push_str(&format!("--- a/{file_path}\n"));
+    hunk.push_str(&format!("+++ b/{file_path}\n"));
+    hunk.push_str(&format!(
+        "@@ -{},{} +{},{} @@\n",
+        prefix + 1,
+        old_changed.len(),
+        prefix + 1,
+        new_changed.len(),
+    ));
+    for line in old_changed {
+        hunk.push('-');
+        hunk.push_str(line);
+        if !line.ends_with('\n') {
+            hunk.push('\n');
+        }
+    }
+    for line in new_changed {
+        hunk.push('+');
+        hunk.push_str(line);
+        if !line.ends_with('\n') {
+            hunk.push('\n');
+        }
+    }
+    hunk
 }
 
 /// Replace the place holders in the header template with the values from the config.
@@ -455,9 +1489,18 @@ fn update_header(file: &mut File, exisiting_header: &str, license_header: &str)
 /// Args:
 ///     - config: Reference to the `Config` with the header template and values to replace
 ///         placeholders with.
+///     - style: The comment style to render the header in.
+///     - license_year: The value to fill `{year}` with for this file.
 ///
 /// Returns: The header with template placeholders filled out.
-fn format_header(config: &Config) -> String {
+fn format_header(config: &Config, style: &CommentStyle, license_year: i64) -> String {
+    format_header_with_year(config, style, &license_year.to_string())
+}
+
+/// Like [`format_header`], but fills `{year}` with the string `year_value` instead
+/// of a single numeric year. Lets an outdated header be rewritten with a year
+/// range (e.g. `"2015-2025"`) that preserves the original start year found in the file.
+fn format_header_with_year(config: &Config, style: &CommentStyle, year_value: &str) -> String {
     // there has already been a check for a header so unwrap is safe.
     let mut header = config.license_header_template.as_ref().unwrap().clone();
 
@@ -473,30 +1516,26 @@ fn format_header(config: &Config) -> String {
         header = header.replace("{licensee}", config.licensee.as_ref().unwrap());
     }
 
-    header = header.replace("{year}", &config.license_year.to_string());
+    header = header.replace("{year}", year_value);
 
-    // Add # to the beginning of each line of the license header if it did not contain one.
-    header
-        .lines()
-        .map(|line| {
-            let line = line.trim();
-            if !line.starts_with(COMMENT) {
-                COMMENT.to_string() + " " + line + "\n"
-            } else {
-                line.to_string() + "\n"
-            }
-        })
-        .collect::<String>()
+    style.format_block(&header)
 }
 
-/// Recursively finds all the python files in a directory ignoring the following dirs:
-///     - *.egg-info/, dist/, __pycache__/, and hidden directories and files.
+/// Recursively finds all the source files with a recognized extension in a directory,
+/// skipping any file or directory matched by `ignore`.
 ///
 /// Args:
-///     - cur_dir: The current directory where python files are being searched for.
-///     - python_files: Vector being used to accumulate found python files.
-///     - ingore_fitd: Array containing the regex for the directories to ignore.
-fn find_python_files(cur_dir: PathBuf, python_files: &mut Vec<PathBuf>, ignore_dirs: &[Regex; 4]) {
+///     - cur_dir: The current directory where source files are being searched for.
+///     - source_files: Vector being used to accumulate found source files.
+///     - ignore: The gitignore-style patterns to skip directories and files with.
+///     - extensions: The file extension -> comment style table; only files whose
+///         extension is a key in this table are collected.
+fn find_source_files(
+    cur_dir: PathBuf,
+    source_files: &mut Vec<PathBuf>,
+    ignore: &IgnoreMatcher,
+    extensions: &HashMap<String, CommentStyle>,
+) {
     match read_dir(cur_dir) {
         // TODO: maybe handle failing to read directory differently?
         Err(_) => {}
@@ -505,15 +1544,18 @@ fn find_python_files(cur_dir: PathBuf, python_files: &mut Vec<PathBuf>, ignore_d
             .for_each(|entry: DirEntry| {
                 let path = entry.path();
                 let name = entry.file_name().into_string().unwrap();
+                let is_dir = path.is_dir();
 
-                // make suree that the path is a directory and not one of the
-                // ones to ignore, then recusively check if it has python files.
-                if path.is_dir() && !ignore_dirs.iter().any(|re| re.is_match(&name)) {
-                    find_python_files(path, python_files, ignore_dirs);
-                } else if let Some(ext) = path.extension()
-                    && ext == "py"
+                if ignore.is_ignored(&path, &name, is_dir) {
+                    return;
+                }
+
+                if is_dir {
+                    find_source_files(path, source_files, ignore, extensions);
+                } else if let Some(ext) = path.extension().and_then(|e| e.to_str())
+                    && extensions.contains_key(ext)
                 {
-                    python_files.push(path);
+                    source_files.push(path);
                 }
             }),
     }
@@ -533,17 +1575,39 @@ fn test_format() {
         licensee: Some("Acme Corp".to_string()),
         license_year: 2025,
         avoid: vec![],
+        allow: vec![],
+        exceptions: std::collections::HashMap::new(),
+        clarifications: vec![],
+        use_current_year: false,
+        comment_styles: crate::comment_style::default_styles(),
+        fuzzy_header_threshold: 0.8,
+        spdx_mode: false,
+        accepted_spdx_licenses: vec![],
+        use_vcs_year: false,
+        ignore_patterns: vec![],
+        newline_style: crate::utils::NewlineStyle::Auto,
+        ensure_trailing_newline: true,
+        collapse_trailing_blank_lines: false,
+        skip_patterns: vec![],
+        advisory_db: None,
+        project_license: None,
+        project_license_files: vec![],
+        default_bsd_license: "BSD-3-Clause".to_string(),
     };
 
-    let header = format_header(&config);
+    let style = CommentStyle::line("#");
+    let header = format_header(&config, &style, config.license_year);
     assert_eq!("# 2025 Acme Corp\n".to_string(), header);
 
     let test_formatter = Formatter {
         files: vec![],
-        header,
         config: config.clone(),
+        remove: false,
         silent: true,
         dry_run: false,
+        skip_matcher: IgnoreMatcher::from_patterns(&[]),
+        check: false,
+        check_format: CheckFormat::Diff,
     };
 
     let test_fixtures = [
@@ -572,7 +1636,7 @@ fn test_format() {
             0,
             LicenseCheckRes::Missing,
             true,
-            "# 2025 Acme Corp\n\n# Comment",
+            "# 2025 Acme Corp\n\n# Comment\n",
         ),
         (
             // 4
@@ -581,7 +1645,7 @@ fn test_format() {
             0,
             LicenseCheckRes::Missing,
             true,
-            "# 2025 Acme Corp\n\n\n# Comment",
+            "# 2025 Acme Corp\n\n\n# Comment\n",
         ),
         (
             // 4
@@ -590,7 +1654,7 @@ fn test_format() {
             18,
             LicenseCheckRes::Missing,
             true,
-            "#!/usr/bin/python\n# 2025 Acme Corp\n\n# Comment",
+            "#!/usr/bin/python\n# 2025 Acme Corp\n\n# Comment\n",
         ),
         (
             // 5
@@ -608,7 +1672,7 @@ fn test_format() {
             18,
             LicenseCheckRes::Outdated,
             true,
-            "#!/usr/bin/python\n\n# 2025 Acme Corp\n\n",
+            "#!/usr/bin/python\n\n# 2024-2025 Acme Corp\n\n",
         ),
         (
             // 7
@@ -617,7 +1681,7 @@ fn test_format() {
             18,
             LicenseCheckRes::Outdated,
             true,
-            "#!/usr/bin/python\n#\n# 2025 Acme Corp\n\n",
+            "#!/usr/bin/python\n#\n# 2024-2025 Acme Corp\n\n",
         ),
         (
             // 8
@@ -626,7 +1690,7 @@ fn test_format() {
             18,
             LicenseCheckRes::Outdated,
             true,
-            "#!/usr/bin/python\n\n# 2025 Acme Corp\n\n# \n",
+            "#!/usr/bin/python\n\n# 2024-2025 Acme Corp\n\n# \n",
         ),
         (
             // 9
@@ -635,7 +1699,7 @@ fn test_format() {
             18,
             LicenseCheckRes::Outdated,
             true,
-            "#!/usr/bin/python\n# 2025 Acme Corp\n\n\n# More Comment\n",
+            "#!/usr/bin/python\n# 2024-2025 Acme Corp\n\n\n# More Comment\n",
         ),
         (
             // 10
@@ -644,8 +1708,8 @@ fn test_format() {
             18,
             LicenseCheckRes::Outdated,
             true,
-            "#!/usr/bin/python\n\n# 2025 Acme Corp\n\n# Wrong format\n",
-        ), // insert_header does not add new line to the end of the file
+            "#!/usr/bin/python\n\n# 2024-2025 Acme Corp\n\n# Wrong format\n",
+        ),
     ];
 
     for (
@@ -664,7 +1728,7 @@ fn test_format() {
         tmp.write_all(content.as_bytes()).unwrap();
         tmp.seek(SeekFrom::Start(0)).unwrap();
 
-        let (first_comment, insert_at) = find_first_comment(&tmp);
+        let (first_comment, insert_at) = find_first_comment(&tmp, &style, "\n");
         assert_eq!(
             expected_comment, first_comment,
             "[find_first_comment first_comment] Failed for fixture {}.",
@@ -677,7 +1741,7 @@ fn test_format() {
         );
         let x: Vec<&str> = first_comment.lines().collect();
         debug!("{x:?}");
-        let (_, check_res) = check_license(&first_comment, &config);
+        let (_, check_res, _) = check_license(&first_comment, &config, &style, config.license_year);
         assert_eq!(
             check_res, expected_check_res,
             "[check license] Failed for fixture {}.",
@@ -702,4 +1766,105 @@ fn test_format() {
         tmp.set_len(0).unwrap();
         tmp.seek(SeekFrom::Start(0)).unwrap();
     }
+
+    // `NewlineStyle::Auto` should pick the file's dominant line ending.
+    assert_eq!(detect_line_ending("# header\r\n\r\ncode\r\n"), "\r\n");
+    assert_eq!(detect_line_ending("# header\n\ncode\n"), "\n");
+    assert_eq!(detect_line_ending(""), "\n");
+
+    // `finalize_content` trailing-newline/blank-line behavior.
+    assert_eq!(finalize_content("a\nb".to_string(), &config, "\n"), "a\nb\n");
+    assert_eq!(finalize_content("a\nb\n".to_string(), &config, "\n"), "a\nb\n");
+    let mut collapsing_config = config.clone();
+    collapsing_config.collapse_trailing_blank_lines = true;
+    assert_eq!(
+        finalize_content("a\nb\n\n\n".to_string(), &collapsing_config, "\n"),
+        "a\nb\n"
+    );
+
+    // An inline `licensepy: skip-file` directive short-circuits the check.
+    let (_, skip_res, _) = check_license(
+        "# licensepy: skip-file\n",
+        &config,
+        &style,
+        config.license_year,
+    );
+    assert_eq!(skip_res, LicenseCheckRes::Skipped);
+
+    // A file matched by `skip_patterns` is left untouched by `format_file`.
+    tmp.seek(SeekFrom::Start(0)).unwrap();
+    tmp.write_all(b"# Comment").unwrap();
+    tmp.seek(SeekFrom::Start(0)).unwrap();
+    let mut skip_formatter = test_formatter;
+    skip_formatter.skip_matcher = IgnoreMatcher::from_patterns(&[
+        path.file_name().unwrap().to_string_lossy().into_owned(),
+    ]);
+    assert!(!skip_formatter.format_file(&path));
+    let mut buffer = String::new();
+    tmp.seek(SeekFrom::Start(0)).unwrap();
+    tmp.read_to_string(&mut buffer).unwrap();
+    assert_eq!(buffer, "# Comment");
+
+    // `proposed_edit` computes the same edit `format_file` applies, without
+    // touching a file - the shared basis for `--check`'s emitters.
+    let missing_edit = proposed_edit(
+        LicenseCheckRes::Missing,
+        "",
+        None,
+        config.license_year,
+        "# Comment",
+        0,
+        &style,
+        &config,
+        "\n",
+        false,
+    );
+    assert_eq!(missing_edit.as_deref(), Some("# 2025 Acme Corp\n\n# Comment\n"));
+    assert!(
+        unified_diff("# Comment", missing_edit.as_ref().unwrap(), "x.py")
+            .contains("+# 2025 Acme Corp")
+    );
+    assert_eq!(
+        proposed_edit(
+            LicenseCheckRes::Found,
+            "",
+            None,
+            config.license_year,
+            "",
+            0,
+            &style,
+            &config,
+            "\n",
+            false
+        ),
+        None
+    );
+
+    // A leading BOM is skipped, and `insert_at` accounts for its byte length.
+    tmp.seek(SeekFrom::Start(0)).unwrap();
+    tmp.write_all("\u{FEFF}# 2024 Acme Corp".as_bytes()).unwrap();
+    tmp.seek(SeekFrom::Start(0)).unwrap();
+    let (bom_comment, bom_insert_at) = find_first_comment(&tmp, &style, "\n");
+    assert_eq!(bom_comment, "# 2024 Acme Corp\n");
+    assert_eq!(bom_insert_at, '\u{FEFF}'.len_utf8());
+    tmp.set_len(0).unwrap();
+    tmp.seek(SeekFrom::Start(0)).unwrap();
+
+    // An XML declaration prologue is skipped like a hashbang, and HTML/XML's
+    // `<!-- -->` block style (new in `default_styles`) is recognized like any
+    // other `CommentStyle`.
+    let html_style = CommentStyle::block("<!--", "-->");
+    assert_eq!(
+        crate::comment_style::default_styles().get("html"),
+        Some(&html_style)
+    );
+    let xml_content = "<?xml version=\"1.0\"?>\n<!-- 2024 Acme Corp -->";
+    tmp.seek(SeekFrom::Start(0)).unwrap();
+    tmp.write_all(xml_content.as_bytes()).unwrap();
+    tmp.seek(SeekFrom::Start(0)).unwrap();
+    let (xml_comment, xml_insert_at) = find_first_comment(&tmp, &html_style, "\n");
+    assert_eq!(xml_comment, "<!-- 2024 Acme Corp -->\n");
+    assert_eq!(xml_insert_at, "<?xml version=\"1.0\"?>\n".len());
+    tmp.set_len(0).unwrap();
+    tmp.seek(SeekFrom::Start(0)).unwrap();
 }
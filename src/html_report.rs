@@ -0,0 +1,136 @@
+use crate::metadata::Metadata;
+use std::collections::BTreeMap;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+/// Escape text for safe inclusion in an HTML document.
+fn html_escape(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// Turn a license string into a stable HTML anchor/id fragment.
+fn anchor(license: &str) -> String {
+    html_escape(license).replace(|c: char| !c.is_alphanumeric(), "-")
+}
+
+/// Write a self-contained HTML license report: a sortable summary table of
+/// package -> license -> avoid-status, followed by the full verbatim
+/// license texts grouped by SPDX id with anchor links from the table.
+///
+/// Rows are written pre-sorted by package name, but the document embeds a
+/// small inline script so a reader can click any column header in the
+/// browser to re-sort the table by that column, toggling ascending/descending
+/// on repeated clicks.
+///
+/// Args:
+///     - path: Where to write the HTML document.
+///     - dependencies: The resolved metadata for every dependency found.
+pub fn write_report(path: &Path, dependencies: &[Metadata]) -> io::Result<()> {
+    let mut sorted = dependencies.to_vec();
+    sorted.sort();
+
+    // one verbatim text per canonical license, so packages sharing a license
+    // all anchor to the same appendix entry instead of repeating its text.
+    let mut texts_by_license: BTreeMap<String, &str> = BTreeMap::new();
+    for dep in &sorted {
+        if let Some(text) = &dep.license_text {
+            texts_by_license
+                .entry(dep.license_canonical.join(" & "))
+                .or_insert(text.as_str());
+        }
+    }
+
+    let mut rows = String::new();
+    for dep in &sorted {
+        let license = dep.license_canonical.join(" & ");
+        let status = if dep.bad_license {
+            "fail"
+        } else if dep.waived {
+            "waived"
+        } else {
+            "ok"
+        };
+        rows.push_str(&format!(
+            "<tr class=\"{status}\"><td>{}</td><td>{}</td><td><a href=\"#license-{}\">{}</a></td><td>{status}</td></tr>\n",
+            html_escape(&dep.name),
+            html_escape(&dep.version),
+            anchor(&license),
+            html_escape(&license),
+        ));
+    }
+
+    let mut texts = String::new();
+    for (license, text) in &texts_by_license {
+        texts.push_str(&format!(
+            "<h2 id=\"license-{}\">{}</h2>\n<pre>{}</pre>\n",
+            anchor(license),
+            html_escape(license),
+            html_escape(text),
+        ));
+    }
+
+    let document = format!(
+        r#"<!DOCTYPE html>
+<html lang="en">
+<head>
+<meta charset="utf-8">
+<title>licensepy license report</title>
+<style>
+  body {{ font-family: sans-serif; margin: 2rem; }}
+  table {{ border-collapse: collapse; width: 100%; }}
+  th, td {{ border: 1px solid #ccc; padding: 0.4rem 0.8rem; text-align: left; }}
+  tr.fail {{ background: #fdd; }}
+  tr.waived {{ background: #ffe; }}
+  pre {{ white-space: pre-wrap; background: #f7f7f7; padding: 1rem; }}
+  table th {{ cursor: pointer; user-select: none; }}
+  table th::after {{ content: "⇅"; color: #999; margin-left: 0.3rem; font-size: 0.8em; }}
+</style>
+</head>
+<body>
+<h1>licensepy license report</h1>
+<table id="report-table">
+<thead><tr><th>Package</th><th>Version</th><th>License</th><th>Status</th></tr></thead>
+<tbody>
+{rows}</tbody>
+</table>
+{texts}
+<script>
+  // Click-to-sort: re-orders <tbody> rows by the clicked column's text,
+  // toggling ascending/descending on repeated clicks of the same header.
+  (function () {{
+    var table = document.getElementById("report-table");
+    var headers = table.tHead.rows[0].cells;
+    var ascending = true;
+    var lastColumn = -1;
+
+    for (var i = 0; i < headers.length; i++) {{
+      headers[i].addEventListener("click", function (event) {{
+        var column = Array.prototype.indexOf.call(headers, event.currentTarget);
+        ascending = column === lastColumn ? !ascending : true;
+        lastColumn = column;
+
+        var tbody = table.tBodies[0];
+        var rows = Array.prototype.slice.call(tbody.rows);
+        rows.sort(function (a, b) {{
+          var x = a.cells[column].textContent.trim();
+          var y = b.cells[column].textContent.trim();
+          return ascending ? x.localeCompare(y) : y.localeCompare(x);
+        }});
+        rows.forEach(function (row) {{
+          tbody.appendChild(row);
+        }});
+      }});
+    }}
+  }})();
+</script>
+</body>
+</html>
+"#
+    );
+
+    fs::write(path, document)
+}
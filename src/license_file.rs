@@ -0,0 +1,300 @@
+use regex::Regex;
+use std::collections::HashSet;
+use std::fs::{self, DirEntry};
+use std::path::{Path, PathBuf};
+
+/// Minimum Sørensen–Dice coefficient (over normalized word-shingles) for a
+/// fuzzy match against a canonical license template to be trusted. Below
+/// this, the license is left undetected rather than guessed.
+const DICE_THRESHOLD: f64 = 0.6;
+
+/// A canonical license text used to identify on-disk LICENSE files whose
+/// package metadata didn't declare a license. Each entry pairs an SPDX id with
+/// a distinctive excerpt of that license's text; excerpts are normalized
+/// at comparison time in [`identify_license`].
+struct CanonicalLicense {
+    spdx_id: &'static str,
+    signature: &'static str,
+}
+
+/// Normalize license text for comparison: lowercase, collapse all whitespace,
+/// and drop copyright-holder lines (which vary per-project and would otherwise
+/// prevent a match against the canonical template).
+fn normalize(text: &str) -> String {
+    text.lines()
+        .filter(|line| !line.to_lowercase().trim_start().starts_with("copyright"))
+        .collect::<Vec<_>>()
+        .join(" ")
+        .to_lowercase()
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Bundled table of canonical license texts, normalized at comparison time.
+/// Kept intentionally small; extend as real-world LICENSE files are found
+/// that don't resolve.
+fn canonical_licenses() -> Vec<CanonicalLicense> {
+    vec![
+        CanonicalLicense {
+            spdx_id: "MIT",
+            signature: "Permission is hereby granted, free of charge, to any person obtaining a copy \
+                 of this software and associated documentation files (the \"Software\"), to deal \
+                 in the Software without restriction, including without limitation the rights \
+                 to use, copy, modify, merge, publish, distribute, sublicense, and/or sell \
+                 copies of the Software, and to permit persons to whom the Software is \
+                 furnished to do so, subject to the following conditions: The above copyright \
+                 notice and this permission notice shall be included in all copies or \
+                 substantial portions of the Software. THE SOFTWARE IS PROVIDED \"AS IS\", \
+                 WITHOUT WARRANTY OF ANY KIND, EXPRESS OR IMPLIED",
+        },
+        CanonicalLicense {
+            spdx_id: "Apache-2.0",
+            signature: "Apache License Version 2.0, January 2004 TERMS AND CONDITIONS FOR USE, \
+                 REPRODUCTION, AND DISTRIBUTION",
+        },
+        CanonicalLicense {
+            spdx_id: "BSD-3-Clause",
+            signature: "Redistribution and use in source and binary forms, with or without \
+                 modification, are permitted provided that the following conditions are met: \
+                 Neither the name of",
+        },
+        CanonicalLicense {
+            spdx_id: "ISC",
+            signature: "Permission to use, copy, modify, and/or distribute this software for any \
+                 purpose with or without fee is hereby granted",
+        },
+        CanonicalLicense {
+            spdx_id: "GPL-3.0-only",
+            signature: "GNU GENERAL PUBLIC LICENSE Version 3, 29 June 2007",
+        },
+    ]
+}
+
+/// Enumerate sibling files in `dir` whose names match `LICENSE*`, `LICENCE*`,
+/// `COPYING*`, or `NOTICE*` (case-insensitive).
+///
+/// Args:
+///     - dir: The `.dist-info`/`.egg-info` directory to search.
+///
+/// Returns: The matching file paths.
+pub fn find_license_files(dir: &Path) -> Vec<PathBuf> {
+    let re = Regex::new(r"(?i)^(license|licence|copying|notice)").unwrap();
+    match fs::read_dir(dir) {
+        Err(_) => Vec::new(),
+        Ok(entries) => entries
+            .filter_map(|entry| entry.ok())
+            .filter_map(|entry: DirEntry| {
+                let filename = entry.file_name().into_string().ok()?;
+                if entry.path().is_file() && re.is_match(&filename) {
+                    Some(entry.path())
+                } else {
+                    None
+                }
+            })
+            .collect(),
+    }
+}
+
+/// Word-bigram shingles of normalized text, used as the set basis for the
+/// Sørensen–Dice similarity in [`dice_coefficient`].
+fn shingles(normalized: &str) -> HashSet<(String, String)> {
+    let words: Vec<&str> = normalized.split_whitespace().collect();
+    words
+        .windows(2)
+        .map(|pair| (pair[0].to_string(), pair[1].to_string()))
+        .collect()
+}
+
+/// Sørensen–Dice coefficient between two shingle sets: `2 * |A ∩ B| / (|A| + |B|)`.
+fn dice_coefficient(a: &HashSet<(String, String)>, b: &HashSet<(String, String)>) -> f64 {
+    if a.is_empty() || b.is_empty() {
+        return 0.0;
+    }
+    let intersection = a.intersection(b).count();
+    (2 * intersection) as f64 / (a.len() + b.len()) as f64
+}
+
+/// Identify the SPDX id of a license file's contents by comparing its
+/// normalized text against the bundled canonical license table.
+///
+/// First tries an exact substring match against each canonical signature; if
+/// none contains it, falls back to the closest match by Sørensen–Dice
+/// similarity over normalized word-shingles, trusting it only above
+/// [`DICE_THRESHOLD`] (license texts get reformatted/reflowed often enough
+/// that plain substring matching alone misses many real matches).
+///
+/// Args:
+///     - text: The raw contents of a candidate license file.
+///
+/// Returns: The SPDX id of the match and whether it came from the fuzzy
+///     (shingle-similarity) pass rather than an exact substring match.
+pub fn identify_license(text: &str) -> Option<(String, bool)> {
+    let normalized = normalize(text);
+    let candidates = canonical_licenses();
+
+    if let Some(candidate) = candidates
+        .iter()
+        .find(|candidate| normalized.contains(&normalize(candidate.signature)))
+    {
+        return Some((candidate.spdx_id.to_string(), false));
+    }
+
+    let text_shingles = shingles(&normalized);
+    candidates
+        .iter()
+        .map(|candidate| {
+            let score = dice_coefficient(&text_shingles, &shingles(&normalize(candidate.signature)));
+            (candidate, score)
+        })
+        .max_by(|(_, a), (_, b)| a.total_cmp(b))
+        .filter(|(_, score)| *score >= DICE_THRESHOLD)
+        .map(|(candidate, _)| (candidate.spdx_id.to_string(), true))
+}
+
+/// Scan `dir` for a LICENSE-like file and attempt to identify its SPDX id.
+///
+/// Args:
+///     - dir: The `.dist-info`/`.egg-info` directory to search.
+///
+/// Returns: The discovered file path, identified SPDX id, and whether the
+///     match was inferred via fuzzy similarity rather than an exact match,
+///     for the first license file present that resolves confidently.
+pub fn discover_license(dir: &Path) -> Option<(PathBuf, String, bool)> {
+    find_license_files(dir).into_iter().find_map(|path| {
+        let text = fs::read_to_string(&path).ok()?;
+        identify_license(&text).map(|(spdx_id, inferred)| (path, spdx_id, inferred))
+    })
+}
+
+/// A 64-bit FNV-1a hash of `data`, rendered as lowercase hex. Used to detect
+/// when a package's on-disk LICENSE content has changed since a config
+/// clarification's `file_hash` was recorded; not cryptographic, just stable
+/// and dependency-free.
+fn fnv1a_hex(data: &[u8]) -> String {
+    const OFFSET_BASIS: u64 = 0xcbf29ce484222325;
+    const PRIME: u64 = 0x100000001b3;
+
+    let mut hash = OFFSET_BASIS;
+    for byte in data {
+        hash ^= u64::from(*byte);
+        hash = hash.wrapping_mul(PRIME);
+    }
+    format!("{hash:016x}")
+}
+
+/// Hash the raw contents of the first LICENSE-like file found in `dir`, for
+/// comparison against a config clarification's `expected_file_hash`.
+///
+/// Args:
+///     - dir: The `.dist-info`/`.egg-info` directory to search.
+///
+/// Returns: The hex-encoded hash of the first license file's raw bytes, if
+///     any license file is present.
+pub fn content_hash(dir: &Path) -> Option<String> {
+    let path = find_license_files(dir).into_iter().next()?;
+    let bytes = fs::read(path).ok()?;
+    Some(fnv1a_hex(&bytes))
+}
+
+/// Directories pruned from [`find_project_license_files`]'s walk: build
+/// output, vendored dependencies, and test/doc fixtures that commonly ship
+/// their own (irrelevant) LICENSE-named files.
+const PROJECT_SCAN_EXCLUDED_DIRS: &[&str] = &["target", "vendor", "tests", "examples", ".github"];
+
+/// Case-insensitive filename patterns recognized as a project-level license
+/// artifact, ported from rust2rpm's heuristics: canonical names
+/// (`COPYING*`, `COPYRIGHT*`, `LICEN[CS]E*`, `NOTICE*`, `PATENTS*`) as well as
+/// a bare SPDX id used directly as a filename (`MIT`, `APACHE-2.0`, `GPL-3.0-only`).
+fn project_license_filename_regex() -> Regex {
+    Regex::new(r"(?i)^((copying|copyright|licen[cs]e|notice|patents).*|(apache-2\.0|mit|bsd-.*|mpl-.*|gpl-.*|lgpl-.*|isc|unlicense)(\..*)?)$").unwrap()
+}
+
+/// Recursively collect filenames matching [`project_license_filename_regex`]
+/// under `dir`, pruning [`PROJECT_SCAN_EXCLUDED_DIRS`].
+fn scan_for_project_license_files(dir: &Path, re: &Regex, found: &mut Vec<PathBuf>) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.filter_map(|entry| entry.ok()) {
+        let path = entry.path();
+        let Ok(filename) = entry.file_name().into_string() else {
+            continue;
+        };
+
+        if path.is_dir() {
+            if !PROJECT_SCAN_EXCLUDED_DIRS.contains(&filename.as_str()) {
+                scan_for_project_license_files(&path, re, found);
+            }
+        } else if re.is_match(&filename) {
+            found.push(path);
+        }
+    }
+}
+
+/// Recursively scan a project tree for license-artifact files, to
+/// complement `license_header_template` enforcement (which only checks
+/// per-file headers, not the top-level license itself).
+///
+/// Args:
+///     - root: The project root directory to scan.
+///
+/// Returns: The matching file paths found anywhere under `root`, outside
+///     [`PROJECT_SCAN_EXCLUDED_DIRS`].
+pub fn find_project_license_files(root: &Path) -> Vec<PathBuf> {
+    let re = project_license_filename_regex();
+    let mut found = Vec::new();
+    scan_for_project_license_files(root, &re, &mut found);
+    found
+}
+
+/// Validate a project's on-disk license artifacts against its configured
+/// avoid/allow policy.
+///
+/// Warns when a policy is configured (`license_to_avoid` or `license_to_allow`
+/// is non-empty) but no project license file was found, or when multiple
+/// discovered license files identify as different SPDX licenses — the common
+/// case of a repo whose header policy is satisfied but whose top-level
+/// license artifact is missing or inconsistent.
+///
+/// Args:
+///     - root: The project root directory to scan.
+///     - license_to_avoid: Array of licenses to avoid, from config.
+///     - license_to_allow: Array of licenses explicitly allowed, from config.
+///
+/// Returns: Warning messages to surface to the user; empty if nothing looked wrong.
+pub fn check_project_license_files(
+    root: &Path,
+    license_to_avoid: &[String],
+    license_to_allow: &[String],
+) -> Vec<String> {
+    let files = find_project_license_files(root);
+    let mut warnings = Vec::new();
+
+    if files.is_empty() {
+        if !license_to_avoid.is_empty() || !license_to_allow.is_empty() {
+            warnings.push(
+                "A license avoid/allow policy is configured but no project LICENSE-like file \
+                 was found."
+                    .to_string(),
+            );
+        }
+        return warnings;
+    }
+
+    let identified: HashSet<String> = files
+        .iter()
+        .filter_map(|path| fs::read_to_string(path).ok())
+        .filter_map(|text| identify_license(&text).map(|(spdx_id, _)| spdx_id))
+        .collect();
+
+    if identified.len() > 1 {
+        let mut ids: Vec<&String> = identified.iter().collect();
+        ids.sort();
+        warnings.push(format!(
+            "Found conflicting project license files identifying as different licenses: {ids:?}."
+        ));
+    }
+
+    warnings
+}
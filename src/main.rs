@@ -3,10 +3,17 @@ use std::cmp::min;
 mod argparse;
 use argparse::{Args, Commands};
 
+mod advisory;
 mod check;
+mod comment_style;
 mod format;
+mod html_report;
+mod license_file;
+mod markers;
 mod metadata;
 mod print_output;
+mod report;
+mod spdx;
 mod utils;
 
 use check::run_check;
@@ -25,6 +32,9 @@ fn main() {
             silent,
             fail_print,
             num_threads,
+            output_format,
+            extras,
+            report,
         } => {
             let num_threads = min(MAX_THREADS, *num_threads);
 
@@ -32,15 +42,29 @@ fn main() {
                 .num_threads(num_threads as usize)
                 .build_global()
                 .unwrap();
-            run_check(*recursive, *by_package, *ignore_toml, *silent, *fail_print)
+            run_check(
+                *recursive,
+                *by_package,
+                *ignore_toml,
+                *silent,
+                *fail_print,
+                *output_format,
+                extras,
+                report.as_deref(),
+            )
         }
 
         Commands::Format {
             files,
             licensee,
             license_year,
+            use_current_year,
+            spdx,
+            remove,
             silent,
             dry_run,
+            check,
+            check_format,
             num_threads,
         } => {
             let num_threads = min(MAX_THREADS, *num_threads);
@@ -50,7 +74,18 @@ fn main() {
                 .build_global()
                 .unwrap();
 
-            let formatter = Formatter::new(files, licensee, license_year, *silent, *dry_run);
+            let formatter = Formatter::new(
+                files,
+                licensee,
+                license_year,
+                *use_current_year,
+                *spdx,
+                *remove,
+                *silent,
+                *dry_run,
+                *check,
+                *check_format,
+            );
             formatter.format_files()
         }
     }
@@ -0,0 +1,180 @@
+use crate::utils::meets_version_req;
+use regex::Regex;
+use std::collections::HashSet;
+
+/// The environment a PEP 508 marker is evaluated against.
+#[derive(Debug, Clone)]
+pub struct Environment {
+    pub python_version: [i32; 3],
+    pub sys_platform: String,
+    pub platform_system: String,
+    pub os_name: String,
+    /// The extras selected via `--extras`, against which `extra == '...'`
+    /// marker comparisons are evaluated.
+    pub extras: HashSet<String>,
+}
+
+impl Environment {
+    /// Build an `Environment` describing the current machine/interpreter.
+    ///
+    /// Args:
+    ///     - python_version: The Python3 version in the cwd.
+    ///     - extras: The extras selected via `--extras`.
+    pub fn current(python_version: [i32; 3], extras: HashSet<String>) -> Self {
+        let (sys_platform, platform_system) = match std::env::consts::OS {
+            "linux" => ("linux", "Linux"),
+            "macos" => ("darwin", "Darwin"),
+            "windows" => ("win32", "Windows"),
+            other => (other, other),
+        };
+        let os_name = if cfg!(windows) { "nt" } else { "posix" };
+
+        Environment {
+            python_version,
+            sys_platform: sys_platform.to_string(),
+            platform_system: platform_system.to_string(),
+            os_name: os_name.to_string(),
+            extras,
+        }
+    }
+}
+
+/// A parsed PEP 508 environment marker expression.
+enum MarkerExpr {
+    And(Box<MarkerExpr>, Box<MarkerExpr>),
+    Or(Box<MarkerExpr>, Box<MarkerExpr>),
+    Comparison {
+        var: String,
+        op: String,
+        value: String,
+    },
+}
+
+/// Tokenize a marker expression into `(`, `)`, `and`, `or`, comparison
+/// operators, quoted values, and bare identifiers.
+fn tokenize(marker: &str) -> Vec<String> {
+    let re = Regex::new(
+        r#"(?i)\(|\)|and|or|==|!=|>=|<=|>|<|'[^']*'|"[^"]*"|[A-Za-z_][A-Za-z0-9_.]*"#,
+    )
+    .unwrap();
+    re.find_iter(marker).map(|m| m.as_str().to_string()).collect()
+}
+
+struct Parser {
+    tokens: Vec<String>,
+    pos: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&str> {
+        self.tokens.get(self.pos).map(String::as_str)
+    }
+
+    fn next(&mut self) -> Option<String> {
+        let token = self.tokens.get(self.pos).cloned();
+        self.pos += 1;
+        token
+    }
+
+    fn parse_or(&mut self) -> Option<MarkerExpr> {
+        let mut expr = self.parse_and()?;
+        while self.peek().map(|t| t.eq_ignore_ascii_case("or")) == Some(true) {
+            self.next();
+            let rhs = self.parse_and()?;
+            expr = MarkerExpr::Or(Box::new(expr), Box::new(rhs));
+        }
+        Some(expr)
+    }
+
+    fn parse_and(&mut self) -> Option<MarkerExpr> {
+        let mut expr = self.parse_atom()?;
+        while self.peek().map(|t| t.eq_ignore_ascii_case("and")) == Some(true) {
+            self.next();
+            let rhs = self.parse_atom()?;
+            expr = MarkerExpr::And(Box::new(expr), Box::new(rhs));
+        }
+        Some(expr)
+    }
+
+    fn parse_atom(&mut self) -> Option<MarkerExpr> {
+        if self.peek() == Some("(") {
+            self.next();
+            let expr = self.parse_or()?;
+            if self.peek() != Some(")") {
+                return None;
+            }
+            self.next();
+            return Some(expr);
+        }
+        let var = self.next()?;
+        let op = self.next()?;
+        let value = self.next()?;
+        let value = value.trim_matches(['\'', '"']).to_string();
+        Some(MarkerExpr::Comparison { var, op, value })
+    }
+}
+
+/// Parse a marker substring (the text after the `;` in a `Requires-Dist`
+/// line) into a [`MarkerExpr`].
+fn parse_marker(marker: &str) -> Option<MarkerExpr> {
+    let tokens = tokenize(marker);
+    if tokens.is_empty() {
+        return None;
+    }
+    let mut parser = Parser { tokens, pos: 0 };
+    let expr = parser.parse_or()?;
+    if parser.pos != parser.tokens.len() {
+        return None;
+    }
+    Some(expr)
+}
+
+fn eval_string_cmp(op: &str, lhs: &str, rhs: &str) -> bool {
+    match op {
+        "==" => lhs == rhs,
+        "!=" => lhs != rhs,
+        _ => false,
+    }
+}
+
+fn evaluate(expr: &MarkerExpr, env: &Environment) -> bool {
+    match expr {
+        MarkerExpr::And(lhs, rhs) => evaluate(lhs, env) && evaluate(rhs, env),
+        MarkerExpr::Or(lhs, rhs) => evaluate(lhs, env) || evaluate(rhs, env),
+        MarkerExpr::Comparison { var, op, value } => match var.as_str() {
+            "python_version" | "python_full_version" => {
+                meets_version_req(&format!("{op}{value}"), &env.python_version)
+            }
+            "sys_platform" => eval_string_cmp(op, &env.sys_platform, value),
+            "platform_system" => eval_string_cmp(op, &env.platform_system, value),
+            "os_name" => eval_string_cmp(op, &env.os_name, value),
+            "extra" => {
+                let selected = env.extras.contains(value);
+                match op.as_str() {
+                    "==" => selected,
+                    "!=" => !selected,
+                    _ => false,
+                }
+            }
+            _ => false,
+        },
+    }
+}
+
+/// Evaluate the marker substring after the `;` in a `Requires-Dist` line
+/// against `env`, returning whether the requirement is active.
+///
+/// Unparsable markers are treated as not met, matching the conservative
+/// default of dropping a requirement we can't make sense of.
+///
+/// Args:
+///     - marker: The marker text, e.g. `sys_platform == 'linux' and python_version >= '3.8'`.
+///     - env: The environment to evaluate the marker against.
+///
+/// Returns: Whether the requirement the marker guards is active.
+pub fn eval_marker_str(marker: &str, env: &Environment) -> bool {
+    match parse_marker(marker) {
+        Some(expr) => evaluate(&expr, env),
+        None => false,
+    }
+}
@@ -1,9 +1,45 @@
-#[derive(Default, Debug, Clone, Eq, PartialEq)]
+use crate::spdx::SpdxExpr;
+use std::path::PathBuf;
+
+#[derive(Default, Debug, Clone, Eq, PartialEq, serde::Serialize)]
 pub struct Metadata {
     pub name: String,
+    /// The package's reported version, e.g. "1.2.3". Empty if not found.
+    pub version: String,
+    /// The raw license string(s) as reported by metadata, e.g. `"BSD License"`
+    /// or `"Apache-2.0"`, kept around for display.
     pub license: Vec<String>,
+    /// `license` mapped to canonical SPDX ids via [`canonicalize_license`].
+    /// `bad_license` and license grouping key off this form instead of the
+    /// raw string, so e.g. `"BSD License"` and `"BSD-3-Clause"` are treated
+    /// as the same license.
+    pub license_canonical: Vec<String>,
+    /// The parsed SPDX expression from `License-Expression:`, if the raw license
+    /// text could be parsed as one; otherwise the canonicalized trove-classifier
+    /// licenses joined with `OR`, or the id recovered from an on-disk LICENSE
+    /// file. `None` when no license was found at all.
+    pub license_expr: Option<SpdxExpr>,
+    /// The on-disk LICENSE-like file the license was recovered from, when
+    /// metadata declared none and a bundled license text matched one found on
+    /// disk. `None` when the license came from metadata instead.
+    pub license_source: Option<PathBuf>,
+    /// Whether `license_source`'s match came from fuzzy shingle-similarity
+    /// rather than an exact substring match against the canonical license
+    /// text. Always `false` when `license_source` is `None`.
+    pub license_inferred: bool,
+    /// Verbatim contents of the first LICENSE-like file found alongside the
+    /// package's metadata, if any, regardless of whether metadata itself
+    /// declared a license. Used for the `--report` HTML license text appendix.
+    pub license_text: Option<String>,
     pub requirements: Vec<String>,
     pub bad_license: bool,
+    /// Whether a config clarification waived what would otherwise be a
+    /// `bad_license` failure for this package.
+    pub waived: bool,
+    /// The top-level packages whose transitive dependency closure pulls this
+    /// package in. Only populated for `bad_license` packages when `--recursive`
+    /// resolution ran.
+    pub required_by: Vec<String>,
 }
 
 impl PartialOrd for Metadata {
@@ -17,3 +53,60 @@ impl Ord for Metadata {
         self.name.cmp(&other.name)
     }
 }
+
+/// Map a trove-classifier or legacy license name to a canonical SPDX id.
+///
+/// Handles the handful of spellings that commonly show up in
+/// `Classifier: License :: OSI Approved :: ...` lines (e.g. `"MIT License"`,
+/// `"GNU General Public License v3 (GPLv3)"`) as well as SPDX ids that are
+/// already canonical, which are returned unchanged.
+///
+/// `"BSD License"` alone doesn't say which BSD variant a package means, so it
+/// maps to `default_bsd_license` (`config.default_bsd_license`) rather than a
+/// hardcoded guess.
+///
+/// Unrecognized strings are returned with only a trailing parenthetical
+/// alias dropped, so a new or unusual license still round-trips instead of
+/// being discarded.
+pub fn canonicalize_license(raw: &str, default_bsd_license: &str) -> String {
+    // drop a trailing parenthetical alias, e.g. "... (GPLv3)"
+    let re_paren = regex::Regex::new(r"\s*\([^)]*\)\s*$").unwrap();
+    let stripped = re_paren.replace(raw, "").trim().to_string();
+
+    if raw.eq_ignore_ascii_case("BSD License") || stripped.eq_ignore_ascii_case("BSD License") {
+        return default_bsd_license.to_string();
+    }
+
+    let lookup: &[(&str, &str)] = &[
+        ("MIT License", "MIT"),
+        ("MIT", "MIT"),
+        ("Apache Software License", "Apache-2.0"),
+        ("Apache License 2.0", "Apache-2.0"),
+        ("ISC License (ISCL)", "ISC"),
+        ("Python Software Foundation License", "PSF-2.0"),
+        ("GNU General Public License v2 (GPLv2)", "GPL-2.0-only"),
+        ("GNU General Public License v2", "GPL-2.0-only"),
+        ("GNU General Public License v3 (GPLv3)", "GPL-3.0-only"),
+        ("GNU General Public License v3", "GPL-3.0-only"),
+        ("GNU General Public License v3 or later (GPLv3+)", "GPL-3.0-or-later"),
+        ("GNU Lesser General Public License v3 (LGPLv3)", "LGPL-3.0-only"),
+        ("GNU Lesser General Public License v2 (LGPLv2)", "LGPL-2.0-only"),
+        ("Mozilla Public License 2.0 (MPL 2.0)", "MPL-2.0"),
+    ];
+
+    for (name, spdx_id) in lookup {
+        if raw.eq_ignore_ascii_case(name) || stripped.eq_ignore_ascii_case(name) {
+            return spdx_id.to_string();
+        }
+    }
+
+    // fold the common "GPL-3.0"/"GPLv3" shorthand to the proper SPDX id
+    if stripped.eq_ignore_ascii_case("GPL-3.0") || stripped.eq_ignore_ascii_case("GPLv3") {
+        return "GPL-3.0-only".to_string();
+    }
+    if stripped.eq_ignore_ascii_case("GPL-2.0") || stripped.eq_ignore_ascii_case("GPLv2") {
+        return "GPL-2.0-only".to_string();
+    }
+
+    stripped
+}
@@ -13,12 +13,24 @@ pub fn print_by_package(dependencies: Vec<Metadata>, recursive: bool, fail_print
     sorted_dep.sort();
 
     for dep in &sorted_dep {
-        let license = dep.license.join(" & ");
+        let license = match &dep.license_expr {
+            // prefer the normalized SPDX expression over the raw reported text
+            Some(expr) => expr.to_string(),
+            None => dep.license.join(" & "),
+        };
 
         if dep.bad_license {
             print!("{}  {} ({}) ", "✗".red().bold(), dep.name, license);
         } else if fail_print {
             continue;
+        } else if dep.waived {
+            print!(
+                "{}  {} ({}) [{}] ",
+                "✔".cyan().bold(),
+                dep.name,
+                license,
+                "waived".yellow()
+            );
         } else {
             print!("{}  {} ({}) ", "✔".cyan().bold(), dep.name, license);
         }
@@ -36,24 +48,32 @@ pub fn print_by_package(dependencies: Vec<Metadata>, recursive: bool, fail_print
             }
             print!("]");
         }
+
+        if let Some(source) = &dep.license_source {
+            if dep.license_inferred {
+                print!(" (inferred from {})", source.display());
+            } else {
+                print!(" (detected from {})", source.display());
+            }
+        }
+        if dep.bad_license && !dep.required_by.is_empty() {
+            print!(" <- pulled in by {}", dep.required_by.join(", "));
+        }
         println!();
     }
 }
 
 /// Print results of `licensepy check` grouped by license.
-pub fn print_by_license(
-    dependencies: Vec<Metadata>,
-    license_to_avoid: &[String],
-    recursive: bool,
-    fail_print: bool,
-) {
+pub fn print_by_license(dependencies: Vec<Metadata>, recursive: bool, fail_print: bool) {
     let mut license_map: HashMap<&str, Vec<Metadata>> = HashMap::new();
     let mut dep_map: HashMap<String, bool> = HashMap::new();
     let mut licenses: HashSet<&str> = HashSet::new();
 
     for dep in &dependencies {
         dep_map.insert(dep.name.clone(), dep.bad_license);
-        for license in &dep.license {
+        // group by the canonical SPDX id so e.g. "BSD License" and
+        // "BSD-3-Clause" land in the same bucket instead of splitting it.
+        for license in &dep.license_canonical {
             license_map.entry(license).or_default().push(dep.clone());
             licenses.insert(license);
         }
@@ -65,7 +85,11 @@ pub fn print_by_license(
     for license in sorted_licenses {
         if let Some(deps) = license_map.get(license) {
             let num_deps = deps.len();
-            if license_to_avoid.contains(&license.to_string()) {
+            // use the already-evaluated `bad_license` verdict (SPDX OR/AND/WITH,
+            // the allow list, clarifications, exceptions) rather than a raw
+            // substring match against `license_to_avoid`, so a dependency that
+            // passed on an OR-satisfiable branch isn't misreported as failing.
+            if deps.iter().any(|d| d.bad_license) {
                 println!("---{} [{}]---  {}", license, num_deps, "✗".red().bold());
             } else if fail_print {
                 continue;
@@ -0,0 +1,132 @@
+use crate::metadata::Metadata;
+use serde::Serialize;
+
+/// Render a dependency's declared license as a single normalized string:
+/// its parsed SPDX expression if one was found, otherwise the raw license
+/// strings joined with `AND` (`print_by_package` makes the same choice but
+/// joins the raw strings with ` & `).
+fn license_expression(dep: &Metadata) -> String {
+    match &dep.license_expr {
+        Some(expr) => expr.to_string(),
+        None => dep.license.join(" AND "),
+    }
+}
+
+/// The JSON projection of a [`Metadata`] entry emitted by `--output-format json`.
+#[derive(Serialize)]
+struct JsonEntry<'a> {
+    name: &'a str,
+    version: &'a str,
+    license: String,
+    requirements: &'a [String],
+    bad_license: bool,
+    waived: bool,
+}
+
+impl<'a> From<&'a Metadata> for JsonEntry<'a> {
+    fn from(dep: &'a Metadata) -> Self {
+        JsonEntry {
+            name: &dep.name,
+            version: &dep.version,
+            license: license_expression(dep),
+            requirements: &dep.requirements,
+            bad_license: dep.bad_license,
+            waived: dep.waived,
+        }
+    }
+}
+
+/// Print the resolved dependency set as a JSON array of
+/// `{name, version, license, requirements, bad_license, waived}` objects.
+///
+/// Args:
+///     - dependencies: The resolved metadata for every dependency found.
+pub fn print_json(dependencies: &[Metadata]) {
+    let mut sorted = dependencies.to_vec();
+    sorted.sort();
+
+    let entries: Vec<JsonEntry> = sorted.iter().map(JsonEntry::from).collect();
+    match serde_json::to_string_pretty(&entries) {
+        Ok(json) => println!("{json}"),
+        Err(err) => eprintln!("Failed to serialize dependencies to JSON: {err}"),
+    }
+}
+
+#[derive(Serialize)]
+struct SpdxPackage<'a> {
+    #[serde(rename = "SPDXID")]
+    spdx_id: String,
+    name: &'a str,
+    #[serde(rename = "versionInfo")]
+    version_info: &'a str,
+    #[serde(rename = "licenseDeclared")]
+    license_declared: String,
+}
+
+#[derive(Serialize)]
+struct SpdxRelationship {
+    #[serde(rename = "spdxElementId")]
+    spdx_element_id: String,
+    #[serde(rename = "relationshipType")]
+    relationship_type: &'static str,
+    #[serde(rename = "relatedSpdxElement")]
+    related_spdx_element: String,
+}
+
+#[derive(Serialize)]
+struct SpdxDocument<'a> {
+    #[serde(rename = "spdxVersion")]
+    spdx_version: &'static str,
+    name: &'static str,
+    packages: Vec<SpdxPackage<'a>>,
+    relationships: Vec<SpdxRelationship>,
+}
+
+/// Print the resolved dependency set as an SPDX-style bill-of-materials
+/// document: one package entry per dependency carrying `licenseDeclared`, and
+/// `DEPENDS_ON` relationships derived from `requirements` when `recursive`
+/// metadata was collected.
+///
+/// Args:
+///     - dependencies: The resolved metadata for every dependency found.
+///     - recursive: Whether `requirements` were collected, and so whether
+///         relationship edges should be emitted.
+pub fn print_spdx(dependencies: &[Metadata], recursive: bool) {
+    let mut sorted = dependencies.to_vec();
+    sorted.sort();
+
+    let packages: Vec<SpdxPackage> = sorted
+        .iter()
+        .map(|dep| SpdxPackage {
+            spdx_id: format!("SPDXRef-Package-{}", dep.name),
+            name: &dep.name,
+            version_info: &dep.version,
+            license_declared: license_expression(dep),
+        })
+        .collect();
+
+    let mut relationships: Vec<SpdxRelationship> = Vec::new();
+    if recursive {
+        for dep in &sorted {
+            for req in &dep.requirements {
+                relationships.push(SpdxRelationship {
+                    spdx_element_id: format!("SPDXRef-Package-{}", dep.name),
+                    relationship_type: "DEPENDS_ON",
+                    related_spdx_element: format!("SPDXRef-Package-{req}"),
+                });
+            }
+        }
+    }
+
+    let document = SpdxDocument {
+        spdx_version: "SPDX-2.3",
+        name: "licensepy-sbom",
+        packages,
+        relationships,
+    };
+
+    match serde_json::to_string_pretty(&document) {
+        Ok(json) => println!("{json}"),
+        Err(err) => eprintln!("Failed to serialize SBOM document: {err}"),
+    }
+}
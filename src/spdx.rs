@@ -0,0 +1,178 @@
+use std::collections::HashSet;
+
+/// A parsed SPDX license expression, as found in `License-Expression:` metadata
+/// fields (e.g. `MIT OR Apache-2.0`, `(GPL-2.0-only WITH Classpath-exception-2.0)`).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SpdxExpr {
+    /// A bare license or exception id, e.g. `MIT`, `Apache-2.0`.
+    Id(String),
+    And(Box<SpdxExpr>, Box<SpdxExpr>),
+    Or(Box<SpdxExpr>, Box<SpdxExpr>),
+    /// A license id paired with an exception id via `WITH`.
+    With(Box<SpdxExpr>, String),
+}
+
+/// Tokenize an SPDX expression into `(`, `)`, `AND`, `OR`, `WITH`, and id tokens.
+/// A bare `/`, as used by some packaging tools in place of `OR` (e.g.
+/// `Apache-2.0/MIT`), is tokenized as a synonym for `OR`.
+fn tokenize(expr: &str) -> Vec<String> {
+    let mut tokens: Vec<String> = Vec::new();
+    let mut cur = String::new();
+
+    let flush = |cur: &mut String, tokens: &mut Vec<String>| {
+        if !cur.is_empty() {
+            tokens.push(std::mem::take(cur));
+        }
+    };
+
+    for c in expr.chars() {
+        match c {
+            '(' | ')' => {
+                flush(&mut cur, &mut tokens);
+                tokens.push(c.to_string());
+            }
+            '/' => {
+                flush(&mut cur, &mut tokens);
+                tokens.push("OR".to_string());
+            }
+            c if c.is_whitespace() => flush(&mut cur, &mut tokens),
+            c => cur.push(c),
+        }
+    }
+    flush(&mut cur, &mut tokens);
+
+    tokens
+}
+
+/// Recursive-descent parser over the tokenized expression. `OR` has the lowest
+/// precedence, then `AND`, then `WITH`, matching the SPDX license-expression grammar.
+struct Parser {
+    tokens: Vec<String>,
+    pos: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&str> {
+        self.tokens.get(self.pos).map(String::as_str)
+    }
+
+    fn next(&mut self) -> Option<String> {
+        let token = self.tokens.get(self.pos).cloned();
+        self.pos += 1;
+        token
+    }
+
+    fn parse_or(&mut self) -> Option<SpdxExpr> {
+        let mut expr = self.parse_and()?;
+        while self.peek().map(|t| t.eq_ignore_ascii_case("or")) == Some(true) {
+            self.next();
+            let rhs = self.parse_and()?;
+            expr = SpdxExpr::Or(Box::new(expr), Box::new(rhs));
+        }
+        Some(expr)
+    }
+
+    fn parse_and(&mut self) -> Option<SpdxExpr> {
+        let mut expr = self.parse_with()?;
+        while self.peek().map(|t| t.eq_ignore_ascii_case("and")) == Some(true) {
+            self.next();
+            let rhs = self.parse_with()?;
+            expr = SpdxExpr::And(Box::new(expr), Box::new(rhs));
+        }
+        Some(expr)
+    }
+
+    fn parse_with(&mut self) -> Option<SpdxExpr> {
+        let expr = self.parse_primary()?;
+        if self.peek().map(|t| t.eq_ignore_ascii_case("with")) == Some(true) {
+            self.next();
+            let exception = self.next()?;
+            return Some(SpdxExpr::With(Box::new(expr), exception));
+        }
+        Some(expr)
+    }
+
+    fn parse_primary(&mut self) -> Option<SpdxExpr> {
+        match self.next()?.as_str() {
+            "(" => {
+                let expr = self.parse_or()?;
+                if self.peek() != Some(")") {
+                    return None;
+                }
+                self.next();
+                Some(expr)
+            }
+            id => Some(SpdxExpr::Id(id.to_string())),
+        }
+    }
+}
+
+/// Parse a raw SPDX license expression string into an [`SpdxExpr`] AST.
+///
+/// Returns `None` if the expression is empty or malformed (e.g. unbalanced
+/// parentheses, a dangling operator).
+pub fn parse_expression(expr: &str) -> Option<SpdxExpr> {
+    let tokens = tokenize(expr);
+    if tokens.is_empty() {
+        return None;
+    }
+    let mut parser = Parser { tokens, pos: 0 };
+    let parsed = parser.parse_or()?;
+    if parser.pos != parser.tokens.len() {
+        return None;
+    }
+    Some(parsed)
+}
+
+/// Evaluate `expr` against an explicit `allow` list alongside the usual
+/// `avoid` denylist, so a compound expression like `MIT OR GPL-3.0` can be
+/// accepted on its `MIT` branch without disabling the check for `GPL-3.0`
+/// everywhere else.
+///
+/// - `Id` is allowed if it isn't in `avoid` and, when `allow` is non-empty,
+///   is also in `allow` (an empty `allow` list falls back to the plain
+///   denylist behavior: anything not in `avoid` is permitted).
+/// - `And` requires every operand to be allowed (all terms are simultaneously
+///   required).
+/// - `Or` is allowed if either operand is (the consumer may pick either).
+/// - `With` inherits the allowedness of its left license id.
+///
+/// `avoid` always wins over `allow` on any branch it matches.
+pub fn allowed(expr: &SpdxExpr, allow: &HashSet<String>, avoid: &HashSet<String>) -> bool {
+    match expr {
+        SpdxExpr::Id(id) => !avoid.contains(id) && (allow.is_empty() || allow.contains(id)),
+        SpdxExpr::And(lhs, rhs) => allowed(lhs, allow, avoid) && allowed(rhs, allow, avoid),
+        SpdxExpr::Or(lhs, rhs) => allowed(lhs, allow, avoid) || allowed(rhs, allow, avoid),
+        SpdxExpr::With(lhs, _) => allowed(lhs, allow, avoid),
+    }
+}
+
+/// Render the parsed expression back to its normalized SPDX text form.
+impl std::fmt::Display for SpdxExpr {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SpdxExpr::Id(id) => write!(f, "{id}"),
+            SpdxExpr::And(lhs, rhs) => {
+                // `OR` binds looser than `AND`, so a nested `OR` needs parentheses
+                let wrap = |e: &SpdxExpr| match e {
+                    SpdxExpr::Or(..) => format!("({e})"),
+                    _ => e.to_string(),
+                };
+                write!(f, "{} AND {}", wrap(lhs), wrap(rhs))
+            }
+            SpdxExpr::Or(lhs, rhs) => write!(f, "{lhs} OR {rhs}"),
+            SpdxExpr::With(lhs, exception) => match **lhs {
+                SpdxExpr::Id(_) => write!(f, "{lhs} WITH {exception}"),
+                _ => write!(f, "({lhs}) WITH {exception}"),
+            },
+        }
+    }
+}
+
+/// Serializes as its normalized SPDX text form rather than the AST, since
+/// that's what consumers of `--output-format json`/`spdx` want.
+impl serde::Serialize for SpdxExpr {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.to_string())
+    }
+}
@@ -1,21 +1,138 @@
+use crate::comment_style::{CommentStyle, default_styles};
 use chrono::{Datelike, Utc};
+use regex::Regex;
+use serde::Deserialize;
+use std::collections::HashMap;
 use std::fs::read_to_string;
 use std::path::Path;
 use std::process::Command;
-use toml::Table;
+
+#[derive(Debug, Clone)]
+/// A per-package license clarification, optionally scoped to a version constraint
+/// (e.g. `">=1.2.3"`), modeled on cargo-deny's `[[licenses.clarify]]` entries.
+///
+/// - package: The dependency name this clarification applies to.
+/// - version_req: Optional version constraint; the clarification only applies to
+///     versions of `package` that meet it. `None` matches every version.
+/// - license: Forces this license value for the package, overriding (or filling
+///     in for) whatever the package's own metadata reports.
+/// - allow: Whether an otherwise-forbidden license should be waived for this
+///     package/version rather than flagged as `bad_license`.
+/// - expected_file_hash: If set, the clarification is only honored when the
+///     package's on-disk LICENSE file hashes to this value, so a stale
+///     override can't silently mask a license change across an upgrade.
+pub struct Clarification {
+    pub package: String,
+    pub version_req: Option<String>,
+    pub license: Option<String>,
+    pub allow: bool,
+    pub expected_file_hash: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// The line ending the header formatter writes, analogous to rustfmt's
+/// `newline_style`.
+pub enum NewlineStyle {
+    /// Detect the dominant line ending already in the file (majority of
+    /// `\r\n` vs lone `\n`) and match it. The default.
+    Auto,
+    /// Always write `\n`.
+    Unix,
+    /// Always write `\r\n`.
+    Windows,
+    /// Match the line ending native to the platform running licensepy.
+    Native,
+}
 
 #[derive(Debug, Clone)]
 /// Used for configuring the license check and header check/formatter.
 ///
 /// - avoid: The license types to be flagged by the license check if found.
+/// - allow: License ids that are explicitly permitted, letting a compound
+///     expression (`MIT OR GPL-3.0`) pass on an allowed branch even though
+///     another branch is also in `avoid`. An empty list falls back to the
+///     plain denylist behavior, i.e. anything not in `avoid` is permitted.
+/// - exceptions: Per-package license tolerances, mapping a dependency name to
+///     a license that is grandfathered in for that package only, even if it's
+///     in `avoid`. A `"*"` value tolerates any license the package reports.
+///     Unlike `clarifications`, this doesn't override the detected license or
+///     support version constraints — it's a flat, auditable allowlist of
+///     known policy deviations.
+/// - clarifications: Per-package license overrides/exceptions.
 /// - license_header_template: The template to use for the license header.
 /// - license_year: The value of the year to replace the `{year}` field in the template.
 /// - licensee: The value of the licensee to replace the `{licensee}` field in the template.
+/// - use_current_year: Whether to always use the current calendar year for `{year}`,
+///     overriding `license_year` from config or the command line.
+/// - comment_styles: File extension -> comment style table used by the header
+///     formatter, seeded from [`default_styles`] and extended/overridden by
+///     `[[comment_styles]]` entries in the config.
+/// - fuzzy_header_threshold: The minimum normalized Levenshtein similarity
+///     ratio (0.0-1.0) a found header must have against the filled template,
+///     once an exact match fails, to be treated as a drifted-but-repairable
+///     header (`Outdated`) rather than `Missing`.
+/// - spdx_mode: Whether the header formatter recognizes and manages
+///     `SPDX-FileCopyrightText:`/`SPDX-License-Identifier:` tag lines instead
+///     of matching `license_header_template`.
+/// - accepted_spdx_licenses: The SPDX license expressions accepted for the
+///     `SPDX-License-Identifier:` tag in `spdx_mode`. The first entry is the
+///     canonical identifier written when inserting a new header. Must be
+///     non-empty in `spdx_mode`: the formatter exits with an error otherwise.
+/// - use_vcs_year: Whether to derive each file's `{year}` from that file's
+///     last commit in git (falling back to `license_year` when git is
+///     unavailable or the file is untracked) instead of using `license_year`
+///     for every file. Overridden by `use_current_year`.
+/// - ignore_patterns: Extra gitignore-style patterns, layered on top of the
+///     formatter's built-in defaults (and any `.licensepyignore` file), used
+///     to skip directories and files during the recursive source-file walk.
+/// - newline_style: The line ending used when writing an inserted/updated
+///     header, so a CRLF file isn't corrupted with a mixed-ending splice.
+/// - ensure_trailing_newline: Whether a rewritten file is given a terminating
+///     line ending if it's missing one. Defaults to true.
+/// - collapse_trailing_blank_lines: Whether multiple trailing blank lines are
+///     collapsed down to a single terminating line ending, rather than just
+///     ensuring one is present. Defaults to false.
+/// - skip_patterns: Gitignore-style patterns for files that are opted out of
+///     header enforcement entirely (reported and rewritten as neither
+///     `Outdated` nor `Missing`), the config-driven counterpart to an inline
+///     `licensepy: skip-file` directive.
+/// - advisory_db: Directory of RustSec-style advisory Markdown files to
+///     cross-reference resolved dependencies against for unpatched
+///     vulnerabilities. `None` disables the advisory check.
+/// - project_license: The project's own declared license, from PEP 621/639
+///     `[project] license`, as an SPDX expression. Supports both the legacy
+///     table form (`{ text = "..." }`/`{ file = "..." }`, whose value is taken
+///     verbatim; for `file` that is the path, not the file's contents) and
+///     the PEP 639 plain SPDX string form.
+/// - project_license_files: Glob patterns from PEP 639 `[project]
+///     license-files`, naming the project's own license artifacts.
+/// - default_bsd_license: The SPDX id [`crate::metadata::canonicalize_license`]
+///     maps the ambiguous legacy trove classifier `"BSD License"` to, since
+///     that classifier alone doesn't distinguish BSD-2-Clause/BSD-3-Clause/
+///     0BSD. Defaults to `"BSD-3-Clause"`, the most common case.
 pub struct Config {
     pub avoid: Vec<String>,
+    pub allow: Vec<String>,
+    pub exceptions: HashMap<String, String>,
+    pub clarifications: Vec<Clarification>,
     pub license_header_template: Option<String>,
     pub license_year: i64,
     pub licensee: Option<String>,
+    pub use_current_year: bool,
+    pub comment_styles: HashMap<String, CommentStyle>,
+    pub fuzzy_header_threshold: f64,
+    pub spdx_mode: bool,
+    pub accepted_spdx_licenses: Vec<String>,
+    pub use_vcs_year: bool,
+    pub ignore_patterns: Vec<String>,
+    pub newline_style: NewlineStyle,
+    pub ensure_trailing_newline: bool,
+    pub collapse_trailing_blank_lines: bool,
+    pub skip_patterns: Vec<String>,
+    pub advisory_db: Option<String>,
+    pub project_license: Option<String>,
+    pub project_license_files: Vec<String>,
+    pub default_bsd_license: String,
 }
 
 impl Config {
@@ -23,16 +140,121 @@ impl Config {
     pub fn default() -> Self {
         Self {
             avoid: vec![],
+            allow: vec![],
+            exceptions: HashMap::new(),
+            clarifications: vec![],
             license_header_template: None,
             license_year: i64::from(Utc::now().year()),
             licensee: None,
+            use_current_year: false,
+            comment_styles: default_styles(),
+            fuzzy_header_threshold: 0.8,
+            spdx_mode: false,
+            accepted_spdx_licenses: vec![],
+            use_vcs_year: false,
+            ignore_patterns: vec![],
+            newline_style: NewlineStyle::Auto,
+            ensure_trailing_newline: true,
+            collapse_trailing_blank_lines: false,
+            skip_patterns: vec![],
+            advisory_db: None,
+            project_license: None,
+            project_license_files: vec![],
+            default_bsd_license: String::from("BSD-3-Clause"),
         }
     }
 }
 
+/// Typed mirror of `pyproject.toml`'s top level: the PEP 621 `[project]`
+/// table and the `[tool.licensepy]` table this crate owns. Deserialized
+/// directly (rather than walked field-by-field off a raw `toml::Table`) so
+/// the nested `[tool]` -> `[tool.licensepy]` path is followed correctly and a
+/// malformed config produces a `serde`/`toml` error message pointing at the
+/// offending key instead of a silent no-op.
+#[derive(Debug, Deserialize)]
+struct PyProjectToml {
+    project: Option<RawProject>,
+    tool: Option<RawTool>,
+}
+
+#[derive(Debug, Deserialize)]
+struct RawTool {
+    licensepy: Option<RawLicensepyConfig>,
+}
+
+/// Typed mirror of `[tool.licensepy]`. Every field is optional (or defaults
+/// to its type's `Default`) so an absent key leaves the corresponding
+/// [`Config`] field at [`Config::default`]'s value rather than being reset.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+struct RawLicensepyConfig {
+    avoid: Vec<String>,
+    allow: Vec<String>,
+    exceptions: HashMap<String, String>,
+    clarifications: Vec<RawClarification>,
+    license_header_template: Option<String>,
+    license_year: Option<i64>,
+    licensee: Option<String>,
+    use_current_year: bool,
+    comment_styles: Vec<RawCommentStyleEntry>,
+    fuzzy_header_threshold: Option<f64>,
+    spdx_mode: bool,
+    accepted_spdx_licenses: Vec<String>,
+    use_vcs_year: bool,
+    ignore_patterns: Vec<String>,
+    newline_style: Option<String>,
+    ensure_trailing_newline: Option<bool>,
+    collapse_trailing_blank_lines: bool,
+    skip_patterns: Vec<String>,
+    advisory_db: Option<String>,
+    default_bsd_license: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct RawCommentStyleEntry {
+    extension: String,
+    block_start: Option<String>,
+    block_end: Option<String>,
+    prefix: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct RawClarification {
+    package: String,
+    version: Option<String>,
+    license: Option<String>,
+    #[serde(default)]
+    allow: bool,
+    file_hash: Option<String>,
+}
+
+/// Typed mirror of the PEP 621 `[project]` table, covering only the license
+/// metadata this crate cross-checks against its own `avoid`/`allow` policy.
+#[derive(Debug, Deserialize)]
+struct RawProject {
+    license: Option<RawProjectLicense>,
+    #[serde(rename = "license-files")]
+    license_files: Option<Vec<String>>,
+}
+
+/// `project.license` accepts two incompatible shapes depending on how
+/// recently the project adopted PEP 639: the legacy `{ text = "..." }`/
+/// `{ file = "..." }` table, or a bare PEP 639 SPDX license expression
+/// string. `untagged` tries each variant in order and picks whichever
+/// matches the TOML value's shape.
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum RawProjectLicense {
+    Spdx(String),
+    Table {
+        text: Option<String>,
+        file: Option<String>,
+    },
+}
+
 /// Read the config toml file from the `pyproject.toml` to extract
 /// the licenses to avoid, license header template, license year, and licensee
-/// if provided.
+/// if provided, as well as the project's own PEP 621/639 license metadata.
 ///
 /// Returns: The config structed with fields filled with the the values from
 ///     the config file.
@@ -45,45 +267,200 @@ pub fn read_config() -> Config {
     }
 
     // read the toml file as a string
-    let toml_str =
-        read_to_string(TOML_FILE).unwrap_or_else(|_| panic!("Failed to read {TOML_FILE} file."));
-    let main_table = toml_str.parse::<Table>().unwrap();
-
-    // extract the licensepy field from the toml table
-    if let Some(licensepy_config) = main_table.get("tool.licensepy")
-        && let Some(table) = licensepy_config.as_table()
-    {
-        // extract the avoid field
-        if let Some(to_avoid) = table.get("avoid").and_then(|v| v.as_array()) {
-            let licenses_to_avoid: Vec<String> = to_avoid
-                .iter()
-                .filter_map(|v| v.as_str().map(|s| s.to_string()))
-                .collect();
-
-            config.avoid = licenses_to_avoid;
-        }
-        // extract the licensee field
-        if let Some(licensee) = table.get("licensee").and_then(|v| v.as_str()) {
-            config.licensee = Some(licensee.to_string());
-        }
+    let toml_str = read_to_string(TOML_FILE)
+        .unwrap_or_else(|err| panic!("Failed to read {TOML_FILE}: {err}"));
+    let pyproject: PyProjectToml = toml::from_str(&toml_str)
+        .unwrap_or_else(|err| panic!("Failed to parse {TOML_FILE}: {err}"));
 
-        // extract the license_year field
-        if let Some(year) = table.get("license_year").and_then(|v| v.as_integer()) {
+    if let Some(licensepy) = pyproject.tool.and_then(|tool| tool.licensepy) {
+        config.avoid = licensepy.avoid;
+        config.allow = licensepy.allow;
+        config.exceptions = licensepy.exceptions;
+        config.clarifications = licensepy
+            .clarifications
+            .into_iter()
+            .map(|entry| Clarification {
+                package: entry.package,
+                version_req: entry.version,
+                license: entry.license,
+                allow: entry.allow,
+                expected_file_hash: entry.file_hash,
+            })
+            .collect();
+        config.license_header_template = licensepy.license_header_template;
+        if let Some(year) = licensepy.license_year {
             config.license_year = year;
         }
+        config.licensee = licensepy.licensee;
+        config.use_current_year = licensepy.use_current_year;
+        if let Some(threshold) = licensepy.fuzzy_header_threshold {
+            config.fuzzy_header_threshold = threshold;
+        }
+        config.spdx_mode = licensepy.spdx_mode;
+        config.accepted_spdx_licenses = licensepy.accepted_spdx_licenses;
+        config.use_vcs_year = licensepy.use_vcs_year;
+        config.ignore_patterns = licensepy.ignore_patterns;
+        if let Some(newline_style) = licensepy.newline_style {
+            config.newline_style = match newline_style.as_str() {
+                "unix" => NewlineStyle::Unix,
+                "windows" => NewlineStyle::Windows,
+                "native" => NewlineStyle::Native,
+                _ => NewlineStyle::Auto,
+            };
+        }
+        if let Some(ensure_trailing_newline) = licensepy.ensure_trailing_newline {
+            config.ensure_trailing_newline = ensure_trailing_newline;
+        }
+        config.collapse_trailing_blank_lines = licensepy.collapse_trailing_blank_lines;
+        config.skip_patterns = licensepy.skip_patterns;
+        config.advisory_db = licensepy.advisory_db;
+        if let Some(default_bsd_license) = licensepy.default_bsd_license {
+            config.default_bsd_license = default_bsd_license;
+        }
 
-        // extract the license_header_template field
-        if let Some(header) = table
-            .get("license_header_template")
-            .and_then(|v| v.as_str())
-        {
-            config.license_header_template = Some(header.to_string());
+        // layer the comment_styles entries on top of the built-in
+        // extension -> style table rather than replacing it.
+        for entry in licensepy.comment_styles {
+            let style = if let (Some(start), Some(end)) = (&entry.block_start, &entry.block_end) {
+                CommentStyle::block(start, end)
+            } else if let Some(prefix) = &entry.prefix {
+                CommentStyle::line(prefix)
+            } else {
+                continue;
+            };
+            config.comment_styles.insert(entry.extension, style);
         }
     }
 
+    if let Some(project) = pyproject.project {
+        config.project_license = project.license.map(|license| match license {
+            RawProjectLicense::Spdx(id) => id,
+            RawProjectLicense::Table { text, file } => text.or(file).unwrap_or_default(),
+        });
+        config.project_license_files = project.license_files.unwrap_or_default();
+    }
+
     config
 }
 
+/// Normalize a package name to PEP 503 form: lowercased, with runs of `-`,
+/// `_`, and `.` collapsed to a single `-`. This lets names like `Foo.Bar` and
+/// `foo-bar` be recognized as the same package when resolving requirements.
+///
+/// Args:
+///     - name: The raw package name.
+///
+/// Returns: The PEP 503 normalized name.
+pub fn normalize_pep503_name(name: &str) -> String {
+    let mut normalized = String::with_capacity(name.len());
+    let mut last_was_sep = false;
+    for c in name.chars() {
+        if c == '-' || c == '_' || c == '.' {
+            last_was_sep = true;
+        } else {
+            if last_was_sep && !normalized.is_empty() {
+                normalized.push('-');
+            }
+            normalized.push(c.to_ascii_lowercase());
+            last_was_sep = false;
+        }
+    }
+    normalized
+}
+
+/// Parse a dotted version string (e.g. "1.2.3") into a 3-component
+/// `[major, minor, patch]` array, filling in any missing or unparsable
+/// components from `default` (mirroring how a partial constraint like ">=3.9"
+/// omits the patch component).
+///
+/// Args:
+///     - version: The version string to parse.
+///     - default: The version whose components backfill missing ones.
+///
+/// Returns: An array of the major, minor, patch version extracted from the version string.
+pub fn parse_version(version: &str, default: &[i32; 3]) -> [i32; 3] {
+    // Cap at 3 components: PEP 440 post/dev-release suffixes (e.g.
+    // "1.0.0.post1") add a 4th dot-separated segment that doesn't fit the
+    // [major, minor, patch] shape, so anything past patch is ignored.
+    let mut parsed_version: Vec<i32> = version
+        .split('.')
+        .take(3)
+        .enumerate()
+        .map(|(index, s)| {
+            s.parse::<i32>()
+                .unwrap_or_else(|_| default.get(index).copied().unwrap_or(0))
+        })
+        .collect();
+
+    let mut diff = 3 - parsed_version.len();
+
+    // if the any of the version numbers are missing, replace with the respective
+    // version number from the default
+    while diff > 0 {
+        parsed_version.push(default[3 - diff]);
+        diff -= 1;
+    }
+
+    parsed_version.try_into().unwrap()
+}
+
+/// Check if a version comparison constraint (e.g. `">=1.2.3"`) is met by `version`.
+/// Used both for Python version markers and semver-style package version
+/// constraints in clarifications.
+///
+/// Args:
+///     - constraint: the constraint to check `version` against.
+///     - version: the version being checked.
+///
+/// Returns: Whether the version constraint was met.
+pub fn meets_version_req(constraint: &str, version: &[i32; 3]) -> bool {
+    let cleaned_constraint = constraint
+        .replace(' ', "")
+        .replace("\'", "")
+        .replace("\"", "");
+
+    let re = Regex::new(r#"(==|<=|>=|!=|<|>)(\d+\.\d+(?:\.\d+)?)"#).unwrap();
+    if let Some(caps) = re.captures(&cleaned_constraint) {
+        // use regex to extract the operator and version string.
+        let operator = &caps[1];
+        let version_str = &caps[2];
+
+        let constraint_version = parse_version(version_str, version);
+
+        match operator {
+            "<=" => *version <= constraint_version,
+            ">=" => *version >= constraint_version,
+            "<" => *version < constraint_version,
+            ">" => *version > constraint_version,
+            "==" => *version == constraint_version,
+            "!=" => *version != constraint_version,
+            _ => false,
+        }
+    } else {
+        false
+    }
+}
+
+#[test]
+fn test_parse_version() {
+    // PEP 440 post/dev-release versions have 4+ dot-separated components;
+    // the trailing ones are ignored rather than indexing past `default`.
+    assert_eq!(parse_version("2024.1.1.1", &[0, 0, 0]), [2024, 1, 1]);
+    assert_eq!(parse_version("1.0.0.post1", &[0, 0, 0]), [1, 0, 0]);
+    // missing/unparsable components backfill from `default`.
+    assert_eq!(parse_version("1.2", &[9, 9, 9]), [1, 2, 9]);
+    assert_eq!(parse_version("1.x.3", &[9, 9, 9]), [1, 9, 3]);
+}
+
+#[test]
+fn test_meets_version_req() {
+    assert!(meets_version_req(">=1.2.3", &[1, 2, 3]));
+    assert!(!meets_version_req(">=1.2.3", &[1, 2, 2]));
+    assert!(meets_version_req("==2.0", &[2, 0, 0]));
+    assert!(meets_version_req("!=1.0.0", &[1, 0, 1]));
+    assert!(!meets_version_req("not-a-constraint", &[1, 0, 0]));
+}
+
 /// Get the Python3 version in the current environment.
 ///
 /// Returns: Array of the major, minor, and patch version.